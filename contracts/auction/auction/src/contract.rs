@@ -2,31 +2,42 @@ use std::collections::VecDeque;
 
 use auction_package::helpers::{verify_admin, AuctionConfig, GetPriceResponse};
 use auction_package::states::{ADMIN, MIN_AUCTION_AMOUNT, TWAP_PRICES};
+use auction_package::{BidderGate, DustDestination};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    coin, to_json_binary, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Uint128,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
 use valence_package::event_indexing::ValenceEvent;
 
 use crate::error::ContractError;
 use crate::execute;
-use crate::helpers::calc_price;
+use crate::helpers::{calc_price, get_dust, is_allowed_bidder};
 use crate::msg::{
-    ExecuteMsg, GetFundsAmountResponse, GetMmResponse, InstantiateMsg, MigrateMsg,
-    NewAuctionParams, QueryMsg,
+    ExecuteMsg, GetAuctionFillsResponse, GetFundsAmountResponse, GetMmResponse, InstantiateMsg,
+    MigrateMsg, NewAuctionParams, QueryMsg,
 };
 use crate::state::{
-    ActiveAuction, ActiveAuctionStatus, AuctionIds, ACTIVE_AUCTION, AUCTION_CONFIG, AUCTION_FUNDS,
-    AUCTION_FUNDS_SUM, AUCTION_IDS, AUCTION_STRATEGY,
+    ActiveAuction, ActiveAuctionStatus, AuctionFillsSummary, AuctionIds, ProviderReceipt,
+    ACCRUED_FEES, ACTIVE_AUCTION, AUCTION_CONFIG, AUCTION_FILLS, AUCTION_FILLS_SUMMARY,
+    AUCTION_FUNDS, AUCTION_FUNDS_SUM, AUCTION_IDS, AUCTION_STRATEGY, BIDDER_ALLOWLIST, BIDDER_GATE,
+    DEPRECATED, DUST_DESTINATION, DUST_SWEEP, PAUSED_AT, PROVIDER_RECEIPTS, RECLAIM_THRESHOLD,
+    SETTLEMENT_MODE, SWEPT_DUST, UNCLAIMED_SETTLEMENTS,
 };
 
 const CONTRACT_NAME: &str = "crates.io:auction";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const TWAP_PRICE_MAX_LEN: u64 = 10;
+/// Default seconds the auction must be paused or the chain halted before funds can be reclaimed
+pub const DEFAULT_RECLAIM_THRESHOLD: u64 = 3 * 24 * 60 * 60;
+/// The default amount of providers we send the dust to in a single `SweepDust`
+pub const DEFAULT_DUST_SWEEP_LIMIT: u64 = 50;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -43,6 +54,14 @@ pub fn instantiate(
     // Verify pair
     msg.pair.verify()?;
 
+    // Verify CW20 denoms are part of the pair and are valid addresses
+    for denom in msg.cw20_denoms.iter() {
+        if denom != &msg.pair.0 && denom != &msg.pair.1 {
+            return Err(ContractError::InvalidCw20Denom(denom.clone()));
+        }
+        deps.api.addr_validate(denom)?;
+    }
+
     // Sort price freshness strategy
     let mut price_freshness_strategy = msg.price_freshness_strategy;
     price_freshness_strategy
@@ -55,6 +74,8 @@ pub fn instantiate(
         pair: msg.pair,
         chain_halt_config: msg.chain_halt_config,
         price_freshness_strategy,
+        protocol_fee: None,
+        cw20_denoms: msg.cw20_denoms,
     };
     AUCTION_CONFIG.save(deps.storage, &auction_config)?;
 
@@ -85,6 +106,7 @@ pub fn instantiate(
             total_amount: Uint128::zero(),
             leftovers: [Uint128::zero(), Uint128::zero()],
             last_checked_block: env.block,
+            reserve_price: None,
         },
     )?;
 
@@ -106,24 +128,45 @@ pub fn execute(
     match msg {
         ExecuteMsg::AuctionFundsManager { sender } => {
             verify_admin(deps.as_ref(), &info)?;
-            execute::auction_funds(deps, &info, sender)
+            let funds = must_pay(&info, &AUCTION_CONFIG.load(deps.storage)?.pair.0)?;
+            execute::auction_funds(deps, funds, sender)
         }
         ExecuteMsg::WithdrawFundsManager { sender } => {
             verify_admin(deps.as_ref(), &info)?;
             execute::withdraw_funds(deps, sender)
         }
-        ExecuteMsg::AuctionFunds {} => execute::auction_funds(deps, &info, info.sender.clone()),
+        ExecuteMsg::AuctionFunds {} => {
+            let funds = must_pay(&info, &AUCTION_CONFIG.load(deps.storage)?.pair.0)?;
+            execute::auction_funds(deps, funds, info.sender)
+        }
+        ExecuteMsg::Receive(msg) => execute::receive_cw20(deps, &env, info, msg),
         ExecuteMsg::WithdrawFunds {} => execute::withdraw_funds(deps, info.sender),
+        ExecuteMsg::ReclaimFundsManager { sender } => {
+            verify_admin(deps.as_ref(), &info)?;
+            execute::reclaim_funds(deps, &env, sender)
+        }
+        ExecuteMsg::ReclaimFunds {} => execute::reclaim_funds(deps, &env, info.sender),
         ExecuteMsg::Admin(admin_msg) => admin::handle_msg(deps, env, info, *admin_msg),
-        ExecuteMsg::Bid {} => execute::do_bid(deps, &info, &env),
+        ExecuteMsg::Bid {} => {
+            let sent_funds = must_pay(&info, &AUCTION_CONFIG.load(deps.storage)?.pair.1)?;
+            execute::do_bid(deps, &env, info.sender, sent_funds)
+        }
         ExecuteMsg::FinishAuction { limit } => execute::finish_auction(deps, &env, limit),
         ExecuteMsg::CleanAfterAuction {} => execute::clean_auction(deps),
+        ExecuteMsg::SweepDust { limit } => execute::sweep_dust(deps, limit),
+        ExecuteMsg::Claim { auction_id, addr } => {
+            let provider = match addr {
+                Some(addr) => deps.api.addr_validate(&addr)?,
+                None => info.sender,
+            };
+            execute::claim(deps, auction_id, provider)
+        }
     }
 }
 
 mod admin {
     use auction_package::helpers::GetPriceResponse;
-    use cosmwasm_std::{coin, BankMsg};
+    use cosmwasm_std::CosmosMsg;
     use valence_package::event_indexing::{ValenceEvent, ValenceGenericEvent};
 
     use crate::msg::AdminMsgs;
@@ -149,6 +192,11 @@ mod admin {
                     },
                 )?;
 
+                // Keep the time of the first pause, so pausing again doesn't reset it
+                if !PAUSED_AT.exists(deps.storage) {
+                    PAUSED_AT.save(deps.storage, &env.block.time)?;
+                }
+
                 let event = ValenceEvent::AuctionPause {};
 
                 Ok(Response::default().add_event(event.into()))
@@ -161,6 +209,7 @@ mod admin {
                         Ok(config)
                     },
                 )?;
+                PAUSED_AT.remove(deps.storage);
 
                 let event = ValenceEvent::AuctionResume {};
 
@@ -200,6 +249,95 @@ mod admin {
 
                 Ok(Response::default().add_event(event.into()))
             }
+            AdminMsgs::UpdateSettlementMode(mode) => {
+                // Switching to claim mode while in the middle of a push settlement is allowed,
+                // providers that were already resolved will not be able to claim again
+                SETTLEMENT_MODE.save(deps.storage, &mode)?;
+
+                let event = ValenceEvent::AuctionUpdateSettlementMode { mode };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateProtocolFee(fee) => {
+                if let Some(fee) = &fee {
+                    fee.verify()?;
+                    deps.api.addr_validate(fee.treasury.as_str())?;
+                }
+
+                AUCTION_CONFIG.update(
+                    deps.storage,
+                    |mut config| -> Result<AuctionConfig, ContractError> {
+                        config.protocol_fee = fee.clone();
+                        Ok(config)
+                    },
+                )?;
+
+                let event = ValenceEvent::AuctionUpdateProtocolFee { fee };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateReclaimThreshold { seconds } => {
+                RECLAIM_THRESHOLD.save(deps.storage, &seconds)?;
+
+                let event = ValenceEvent::AuctionUpdateReclaimThreshold { seconds };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateDustDestination(destination) => {
+                match &destination {
+                    Some(destination) => {
+                        if let DustDestination::Recipient(recipient) = destination {
+                            deps.api.addr_validate(recipient.as_str())?;
+                        }
+                        DUST_DESTINATION.save(deps.storage, destination)?;
+                    }
+                    None => DUST_DESTINATION.remove(deps.storage),
+                }
+
+                let event = ValenceEvent::AuctionUpdateDustDestination { destination };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateBidderGate(gate) => {
+                match &gate {
+                    Some(gate) => {
+                        if let BidderGate::Cw4Group(group) = gate {
+                            deps.api.addr_validate(group.as_str())?;
+                        }
+                        BIDDER_GATE.save(deps.storage, gate)?;
+                    }
+                    None => BIDDER_GATE.remove(deps.storage),
+                }
+
+                let event = ValenceEvent::AuctionUpdateBidderGate { gate };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateBidderAllowlist { add, remove } => {
+                for addr in add.iter() {
+                    BIDDER_ALLOWLIST.save(
+                        deps.storage,
+                        deps.api.addr_validate(addr)?,
+                        &Empty {},
+                    )?;
+                }
+
+                for addr in remove.iter() {
+                    BIDDER_ALLOWLIST.remove(deps.storage, deps.api.addr_validate(addr)?);
+                }
+
+                let event = ValenceEvent::AuctionUpdateBidderAllowlist { add, remove };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::Deprecate => {
+                DEPRECATED.save(deps.storage, &true)?;
+
+                let event = ValenceEvent::AuctionDeprecate {};
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::RefundLeftovers { limit } => execute::refund_leftovers(deps, limit),
         }
     }
 
@@ -255,7 +393,7 @@ mod admin {
             return do_refund(
                 deps,
                 auction_ids.curr,
-                config.pair.0.clone(),
+                &config,
                 min_start_auction,
                 total_funds,
             );
@@ -278,10 +416,20 @@ mod admin {
             total_amount: total_funds,
             leftovers: [Uint128::zero(), Uint128::zero()],
             last_checked_block: env.block.clone(),
+            reserve_price: new_auction_params.reserve_price,
         };
 
         ACTIVE_AUCTION.save(deps.storage, &new_active_auction)?;
 
+        AUCTION_FILLS_SUMMARY.save(
+            deps.storage,
+            auction_ids.curr,
+            &AuctionFillsSummary {
+                total_amount: total_funds,
+                ..Default::default()
+            },
+        )?;
+
         let event = ValenceGenericEvent::<ActiveAuction>::AuctionOpen {
             auction_id: auction_ids.curr,
             auction: new_active_auction,
@@ -296,35 +444,47 @@ mod admin {
     fn do_refund(
         deps: DepsMut,
         auction_id: u64,
-        denom: String,
+        config: &AuctionConfig,
         min_amount: Uint128,
         total_funds: Uint128,
     ) -> Result<Response, ContractError> {
-        let bank_msgs = AUCTION_FUNDS
+        let funds = AUCTION_FUNDS
             .prefix(auction_id)
             .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .map(|fund| {
-                let (addr, amount) = fund.unwrap();
-                Ok(BankMsg::Send {
-                    to_address: addr.to_string(),
-                    amount: vec![coin(amount.into(), denom.clone())],
-                })
-            })
-            .collect::<StdResult<Vec<BankMsg>>>()?;
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut send_msgs: Vec<CosmosMsg> = Vec::with_capacity(funds.len());
+        let total_users = funds.len() as u64;
+
+        for (addr, amount) in funds {
+            PROVIDER_RECEIPTS.save(
+                deps.storage,
+                (addr.clone(), auction_id),
+                &ProviderReceipt {
+                    sent: amount,
+                    received: Uint128::zero(),
+                    leftover: amount,
+                },
+            )?;
+
+            send_msgs.extend(
+                config.send_funds_msgs(&addr, vec![coin(amount.into(), config.pair.0.clone())])?,
+            );
+        }
 
         AUCTION_FUNDS_SUM.save(deps.storage, auction_id, &Uint128::zero())?;
-        AUCTION_FUNDS.clear(deps.storage);
+        AUCTION_FUNDS.prefix(auction_id).clear(deps.storage, None);
 
         let event = ValenceEvent::AuctionOpenRefund {
             auction_id,
             min_amount,
             refund_amount: total_funds,
-            total_users: bank_msgs.len() as u64,
+            total_users,
         };
 
         Ok(Response::new()
             .add_event(event.into())
-            .add_messages(bank_msgs))
+            .add_messages(send_msgs))
     }
     /// Helper functions to get the starting and ending prices
     /// Factors in freshness of the price from the oracle
@@ -415,7 +575,65 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let auction_strategy = AUCTION_STRATEGY.load(deps.storage)?;
             to_json_binary(&auction_strategy)
         }
+        QueryMsg::GetSettlementMode => {
+            to_json_binary(&SETTLEMENT_MODE.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::GetReclaimThreshold => to_json_binary(
+            &RECLAIM_THRESHOLD
+                .may_load(deps.storage)?
+                .unwrap_or(DEFAULT_RECLAIM_THRESHOLD),
+        ),
+        QueryMsg::HasUnclaimedSettlements => to_json_binary(
+            &UNCLAIMED_SETTLEMENTS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_some(),
+        ),
+        QueryMsg::GetDustDestination => to_json_binary(&DUST_DESTINATION.may_load(deps.storage)?),
+        QueryMsg::GetDustSweep => to_json_binary(&DUST_SWEEP.may_load(deps.storage)?),
+        QueryMsg::GetAuctionDust { auction_id } => {
+            if let Some(dust) = SWEPT_DUST.may_load(deps.storage, auction_id)? {
+                return to_json_binary(&dust);
+            }
+
+            let auction_ids = AUCTION_IDS.load(deps.storage)?;
+            let active_auction = ACTIVE_AUCTION.load(deps.storage)?;
+            if auction_id != auction_ids.curr
+                || active_auction.status != ActiveAuctionStatus::AuctionClosed
+            {
+                return to_json_binary(&Vec::<Coin>::new());
+            }
+
+            let config = AUCTION_CONFIG.load(deps.storage)?;
+            to_json_binary(&get_dust(deps, &config.pair, &active_auction.leftovers)?)
+        }
+        QueryMsg::GetBidderGate => to_json_binary(&BIDDER_GATE.may_load(deps.storage)?),
+        QueryMsg::GetBidderAllowlist { start_after, limit } => {
+            let start_after = start_after
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?
+                .map(Bound::exclusive);
+            let allowlist = BIDDER_ALLOWLIST
+                .keys(deps.storage, start_after, None, Order::Ascending)
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&allowlist)
+        }
+        QueryMsg::IsAllowedBidder { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            to_json_binary(&is_allowed_bidder(deps, &addr)?)
+        }
+        QueryMsg::GetAccruedFees => {
+            let config = AUCTION_CONFIG.load(deps.storage)?;
+            let accrued = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
+
+            to_json_binary(&coin(accrued.u128(), config.pair.1))
+        }
         QueryMsg::GetAdmin => to_json_binary(&ADMIN.load(deps.storage)?),
+        QueryMsg::IsDeprecated => {
+            to_json_binary(&DEPRECATED.may_load(deps.storage)?.unwrap_or_default())
+        }
         QueryMsg::GetMmData => {
             let active_auction = ACTIVE_AUCTION.load(deps.storage)?;
             let price = calc_price(&active_auction, env.block.height);
@@ -428,6 +646,46 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 block: env.block,
             })
         }
+        QueryMsg::GetAuctionFills {
+            auction_id,
+            start_after,
+            limit,
+        } => {
+            let start_after = start_after.map(Bound::exclusive);
+            let fills = AUCTION_FILLS
+                .prefix(auction_id)
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            let summary = AUCTION_FILLS_SUMMARY
+                .may_load(deps.storage, auction_id)?
+                .unwrap_or_default();
+
+            to_json_binary(&GetAuctionFillsResponse {
+                fills,
+                vwap: Decimal::checked_from_ratio(summary.total_paid, summary.total_bought)
+                    .unwrap_or_default(),
+                total_resolved: summary.total_paid,
+                fill_ratio: Decimal::checked_from_ratio(summary.total_bought, summary.total_amount)
+                    .unwrap_or_default(),
+            })
+        }
+        QueryMsg::GetProviderHistory {
+            addr,
+            start_after,
+            limit,
+        } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            let start_after = start_after.map(Bound::exclusive);
+            let receipts = PROVIDER_RECEIPTS
+                .prefix(addr)
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&receipts)
+        }
     }
 }
 
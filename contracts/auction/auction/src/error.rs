@@ -1,5 +1,7 @@
 use auction_package::error::AuctionError;
-use cosmwasm_std::{CheckedFromRatioError, DecimalRangeExceeded, OverflowError, StdError, Uint128};
+use cosmwasm_std::{
+    CheckedFromRatioError, Decimal, DecimalRangeExceeded, OverflowError, StdError, Uint128,
+};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -26,15 +28,30 @@ pub enum ContractError {
     #[error("Sender is not admin")]
     NotAdmin,
 
+    #[error("Denom {0} is not a CW20 token of the pair")]
+    InvalidCw20Denom(String),
+
+    #[error("CW20 token {0} can't be used for this message")]
+    InvalidCw20Token(String),
+
     #[error("Auction amount is too low, minimum: {0}")]
     AuctionAmountTooLow(Uint128),
 
     #[error("Auction is paused")]
     AuctionIsPaused,
 
+    #[error("Auction is deprecated")]
+    AuctionIsDeprecated,
+
+    #[error("Auction is not deprecated")]
+    AuctionNotDeprecated,
+
     #[error("Current auction is finished")]
     AuctionFinished,
 
+    #[error("Auction price dropped below the reserve price: {0}")]
+    PriceBelowReserve(Decimal),
+
     #[error("Auction is not closed yet")]
     AuctionNotClosed,
 
@@ -76,6 +93,30 @@ pub enum ContractError {
 
     #[error("End block is smaller or equal to the start block")]
     InvalidAuctionEndBlock,
+
+    #[error("Auction {0} wasn't settled in claim mode, nothing to claim")]
+    NoClaimableAuction(u64),
+
+    #[error("No funds to claim from this auction")]
+    NothingToClaim,
+
+    #[error("Funds can only be reclaimed from the current auction once it was paused or the chain halted for more than {0} seconds")]
+    FundsNotStuck(u64),
+
+    #[error("No funds to reclaim from the current auction")]
+    NoFundsToReclaim,
+
+    #[error("Dust destination is not set")]
+    NoDustDestination,
+
+    #[error("No dust to sweep")]
+    NoDustToSweep,
+
+    #[error("No funds providers to redistribute the dust to")]
+    NoProvidersForDust,
+
+    #[error("No leftovers to refund")]
+    NoLeftoversToRefund,
 }
 
 impl From<ContractError> for StdError {
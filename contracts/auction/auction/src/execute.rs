@@ -1,27 +1,37 @@
 use auction_package::{
+    helpers::AuctionConfig,
     states::{ADMIN, MIN_AUCTION_AMOUNT, TWAP_PRICES},
-    Price, CLOSEST_TO_ONE_POSSIBLE,
+    DustDestination, Price, SettlementMode,
 };
 use cosmwasm_std::{
-    coin, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128,
+    coin, from_json, Addr, Coin, CosmosMsg, Decimal, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Storage, Uint128,
 };
+use cw20::Cw20ReceiveMsg;
 use cw_storage_plus::Bound;
-use cw_utils::must_pay;
 use valence_package::event_indexing::ValenceEvent;
 
 use crate::{
-    contract::TWAP_PRICE_MAX_LEN,
+    contract::{DEFAULT_DUST_SWEEP_LIMIT, DEFAULT_RECLAIM_THRESHOLD, TWAP_PRICE_MAX_LEN},
     error::ContractError,
-    helpers::{calc_buy_amount, calc_price, is_chain_halted},
+    helpers::{
+        calc_buy_amount, calc_price, calc_provider_share, chain_halt_duration, get_dust,
+        is_allowed_bidder, is_below_reserve_price, is_chain_halted,
+    },
+    msg::Cw20HookMsg,
     state::{
-        ActiveAuctionStatus, ACTIVE_AUCTION, AUCTION_CONFIG, AUCTION_FUNDS, AUCTION_FUNDS_SUM,
-        AUCTION_IDS,
+        ActiveAuction, ActiveAuctionStatus, AuctionFill, AuctionSettlement, DustSweep,
+        ProviderReceipt, ACCRUED_FEES, ACTIVE_AUCTION, AUCTION_CONFIG, AUCTION_FILLS,
+        AUCTION_FILLS_SUMMARY, AUCTION_FUNDS, AUCTION_FUNDS_SUM, AUCTION_IDS,
+        AUCTION_PROTOCOL_FEES, AUCTION_SETTLEMENTS, DEPRECATED, DUST_DESTINATION, DUST_PROVIDERS,
+        DUST_PROVIDERS_TOTAL, DUST_SWEEP, PAUSED_AT, PROVIDER_RECEIPTS, RECLAIM_THRESHOLD,
+        SETTLEMENT_MODE, SWEPT_DUST, UNCLAIMED_SETTLEMENTS,
     },
 };
 
 pub(crate) fn auction_funds(
     deps: DepsMut,
-    info: &MessageInfo,
+    funds: Uint128,
     sender: Addr,
 ) -> Result<Response, ContractError> {
     let config = AUCTION_CONFIG.load(deps.storage)?;
@@ -31,7 +41,10 @@ pub(crate) fn auction_funds(
         return Err(ContractError::AuctionIsPaused);
     }
 
-    let funds = must_pay(info, &config.pair.0)?;
+    if DEPRECATED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::AuctionIsDeprecated);
+    }
+
     let min_amount = match MIN_AUCTION_AMOUNT.query(&deps.querier, admin, config.pair.0)? {
         Some(amount) => Ok(amount.send),
         None => Err(ContractError::NoTokenMinAmount),
@@ -41,12 +54,29 @@ pub(crate) fn auction_funds(
         return Err(ContractError::AuctionAmountTooLow(min_amount));
     }
 
-    let next_auction_id: u64 = AUCTION_IDS.load(deps.storage)?.next;
+    let next_auction_id = add_next_auction_funds(deps.storage, &sender, funds)?;
+
+    let event = ValenceEvent::AuctionAuctionFunds {
+        address: sender.to_string(),
+        amount: funds,
+        auction_id: next_auction_id,
+    };
 
-    // Update funds of the sender for next auction
+    Ok(Response::default().add_event(event.into()))
+}
+
+/// Add funds of the provider to the next auction, returns the id of the next auction
+fn add_next_auction_funds(
+    storage: &mut dyn Storage,
+    provider: &Addr,
+    funds: Uint128,
+) -> Result<u64, ContractError> {
+    let next_auction_id: u64 = AUCTION_IDS.load(storage)?.next;
+
+    // Update funds of the provider for next auction
     AUCTION_FUNDS.update(
-        deps.storage,
-        (next_auction_id, sender.clone()),
+        storage,
+        (next_auction_id, provider.clone()),
         |amount| -> Result<Uint128, ContractError> {
             match amount {
                 Some(amount) => Ok(amount.checked_add(funds)?),
@@ -57,7 +87,7 @@ pub(crate) fn auction_funds(
 
     // update the sum of the next auction
     AUCTION_FUNDS_SUM.update(
-        deps.storage,
+        storage,
         next_auction_id,
         |amount| -> Result<Uint128, ContractError> {
             match amount {
@@ -67,19 +97,59 @@ pub(crate) fn auction_funds(
         },
     )?;
 
-    let event = ValenceEvent::AuctionAuctionFunds {
-        address: sender.to_string(),
-        amount: funds,
-        auction_id: next_auction_id,
-    };
+    Ok(next_auction_id)
+}
 
-    Ok(Response::default().add_event(event.into()))
+/// Handle CW20 tokens sent to the auction, `info.sender` is the token contract
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+    let token = info.sender.to_string();
+
+    if !config.is_cw20(&token) {
+        return Err(ContractError::InvalidCw20Token(token));
+    }
+
+    let sender = deps.api.addr_validate(&msg.sender)?;
+    let hook_msg: Cw20HookMsg = from_json(&msg.msg)?;
+
+    match hook_msg {
+        Cw20HookMsg::AuctionFundsManager { sender: provider } => {
+            if token != config.pair.0 {
+                return Err(ContractError::InvalidCw20Token(token));
+            }
+
+            if sender != ADMIN.load(deps.storage)? {
+                return Err(ContractError::NotAdmin);
+            }
+
+            auction_funds(deps, msg.amount, provider)
+        }
+        Cw20HookMsg::AuctionFunds {} => {
+            if token != config.pair.0 {
+                return Err(ContractError::InvalidCw20Token(token));
+            }
+
+            auction_funds(deps, msg.amount, sender)
+        }
+        Cw20HookMsg::Bid {} => {
+            if token != config.pair.1 {
+                return Err(ContractError::InvalidCw20Token(token));
+            }
+
+            do_bid(deps, env, sender, msg.amount)
+        }
+    }
 }
 
 pub fn withdraw_funds(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
     let config = AUCTION_CONFIG.load(deps.storage)?;
 
-    let mut send_funds: Coin = coin(0_u128, config.pair.0);
+    let mut send_funds: Coin = coin(0_u128, &config.pair.0);
     let auction_ids = AUCTION_IDS.load(deps.storage)?;
 
     let funds_amount = AUCTION_FUNDS
@@ -102,11 +172,6 @@ pub fn withdraw_funds(deps: DepsMut, sender: Addr) -> Result<Response, ContractE
         return Err(ContractError::NoFundsToWithdraw);
     }
 
-    let bank_msg = BankMsg::Send {
-        to_address: sender.to_string(),
-        amount: vec![send_funds.clone()],
-    };
-
     let event = ValenceEvent::AuctionWithdrawFunds {
         address: sender.to_string(),
         amount: send_funds.amount,
@@ -115,10 +180,112 @@ pub fn withdraw_funds(deps: DepsMut, sender: Addr) -> Result<Response, ContractE
 
     Ok(Response::default()
         .add_event(event.into())
-        .add_message(bank_msg))
+        .add_messages(config.send_funds_msgs(&sender, vec![send_funds])?))
 }
 
-pub fn do_bid(deps: DepsMut, info: &MessageInfo, env: &Env) -> Result<Response, ContractError> {
+/// Reclaim the share of the provider from the current auction, when the auction is stuck
+/// because it was paused or the chain halted for longer than the reclaim threshold.
+///
+/// The provider receives the provider's share of the unsold pair.0 and of the pair.1 that was
+/// already resolved, and is removed from the auction, so the shares of the other providers
+/// stay the same.
+pub fn reclaim_funds(deps: DepsMut, env: &Env, sender: Addr) -> Result<Response, ContractError> {
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+    let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
+    let threshold = RECLAIM_THRESHOLD
+        .may_load(deps.storage)?
+        .unwrap_or(DEFAULT_RECLAIM_THRESHOLD);
+
+    // Funds of a finished auction are not stuck, they can be resolved by finishing the auction
+    if active_auction.status != ActiveAuctionStatus::Started {
+        return Err(ContractError::FundsNotStuck(threshold));
+    }
+
+    // Auctions paused before the pause time was saved fall back to the last block they were checked
+    let paused_at = match PAUSED_AT.may_load(deps.storage)? {
+        Some(paused_at) => Some(paused_at),
+        None if config.is_paused => Some(active_auction.last_checked_block.time),
+        None => None,
+    };
+    let paused_for = paused_at
+        .map(|paused_at| env.block.time.seconds() - paused_at.seconds())
+        .unwrap_or_default();
+    let halted_for = chain_halt_duration(
+        env,
+        &active_auction.last_checked_block,
+        &config.chain_halt_config,
+    );
+
+    if paused_for <= threshold && halted_for <= threshold {
+        return Err(ContractError::FundsNotStuck(threshold));
+    }
+
+    let auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+    let amount = AUCTION_FUNDS
+        .may_load(deps.storage, (auction_id, sender.clone()))?
+        .ok_or(ContractError::NoFundsToReclaim)?;
+
+    let (unsold_amount, bought_amount) =
+        calc_provider_share(amount, &AuctionSettlement::from(&active_auction))?;
+
+    active_auction.total_amount = active_auction.total_amount.checked_sub(amount)?;
+    active_auction.available_amount = active_auction.available_amount.checked_sub(unsold_amount)?;
+    active_auction.resolved_amount = active_auction.resolved_amount.checked_sub(bought_amount)?;
+    ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
+
+    // Remove the share of the provider from the summary, so the fill ratio and the vwap
+    // are of the funds that stay in the auction
+    let mut summary = AUCTION_FILLS_SUMMARY
+        .may_load(deps.storage, auction_id)?
+        .unwrap_or_default();
+    summary.total_amount = summary.total_amount.saturating_sub(amount);
+    summary.total_bought = summary
+        .total_bought
+        .saturating_sub(amount.checked_sub(unsold_amount)?);
+    summary.total_paid = summary.total_paid.saturating_sub(bought_amount);
+    AUCTION_FILLS_SUMMARY.save(deps.storage, auction_id, &summary)?;
+
+    AUCTION_FUNDS.remove(deps.storage, (auction_id, sender.clone()));
+    AUCTION_FUNDS_SUM.update(
+        deps.storage,
+        auction_id,
+        |sum| -> Result<Uint128, ContractError> {
+            Ok(sum.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    PROVIDER_RECEIPTS.save(
+        deps.storage,
+        (sender.clone(), auction_id),
+        &ProviderReceipt {
+            sent: amount,
+            received: bought_amount,
+            leftover: unsold_amount,
+        },
+    )?;
+
+    let event = ValenceEvent::AuctionReclaimFunds {
+        auction_id,
+        address: sender.to_string(),
+        unsold_amount,
+        bought_amount,
+    };
+
+    Ok(Response::default()
+        .add_event(event.into())
+        .add_messages(provider_send_msgs(
+            &sender,
+            &config,
+            unsold_amount,
+            bought_amount,
+        )?))
+}
+
+pub fn do_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: Addr,
+    sent_funds: Uint128,
+) -> Result<Response, ContractError> {
     // Verify we have an active auction, else error out
     let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
 
@@ -144,9 +311,19 @@ pub fn do_bid(deps: DepsMut, info: &MessageInfo, env: &Env) -> Result<Response,
         return Err(ContractError::AuctionIsPaused);
     }
 
-    let sent_funds = must_pay(info, &config.pair.1)?;
+    if !is_allowed_bidder(deps.as_ref(), &bidder)? {
+        return Err(ContractError::UnauthorizedToBid);
+    }
+
     let curr_price = calc_price(&active_auction, env.block.height);
 
+    // Don't sell below the reserve price, the unsold amount goes to the next auction
+    if let Some(reserve_price) = active_auction.reserve_price {
+        if curr_price < reserve_price {
+            return Err(ContractError::PriceBelowReserve(reserve_price));
+        }
+    }
+
     let (buy_amount, leftover_amount) = if is_chain_halted(
         env,
         &active_auction.last_checked_block,
@@ -196,25 +373,44 @@ pub fn do_bid(deps: DepsMut, info: &MessageInfo, env: &Env) -> Result<Response,
     }
 
     if !buy_amount.is_zero() {
-        send_funds.push(coin(buy_amount.u128(), config.pair.0));
+        send_funds.push(coin(buy_amount.u128(), config.pair.0.clone()));
     }
 
-    let response = if !send_funds.is_empty() {
-        let bank_msg = BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: send_funds,
-        };
-        Response::default().add_message(bank_msg)
-    } else {
-        Response::default()
-    };
+    let response = Response::default().add_messages(config.send_funds_msgs(&bidder, send_funds)?);
 
     active_auction.last_checked_block = env.block.clone();
     ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
 
+    let auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+
+    // Record the fill if the bidder bought anything
+    if !buy_amount.is_zero() {
+        let paid_amount = sent_funds.checked_sub(leftover_amount)?;
+        let mut summary = AUCTION_FILLS_SUMMARY
+            .may_load(deps.storage, auction_id)?
+            .unwrap_or_default();
+
+        AUCTION_FILLS.save(
+            deps.storage,
+            (auction_id, summary.fills),
+            &AuctionFill {
+                bidder: bidder.clone(),
+                block: env.block.height,
+                price: curr_price,
+                bought_amount: buy_amount,
+                paid_amount,
+            },
+        )?;
+
+        summary.fills += 1;
+        summary.total_bought = summary.total_bought.checked_add(buy_amount)?;
+        summary.total_paid = summary.total_paid.checked_add(paid_amount)?;
+        AUCTION_FILLS_SUMMARY.save(deps.storage, auction_id, &summary)?;
+    }
+
     let event = ValenceEvent::AuctionDoBid {
-        auction_id: AUCTION_IDS.load(deps.storage)?.curr,
-        bidder: info.sender.to_string(),
+        auction_id,
+        bidder: bidder.to_string(),
         bought_amount: buy_amount,
         refunded_amount: leftover_amount,
         price: curr_price,
@@ -223,18 +419,20 @@ pub fn do_bid(deps: DepsMut, info: &MessageInfo, env: &Env) -> Result<Response,
     Ok(response.add_event(event.into()))
 }
 
-pub fn finish_auction(deps: DepsMut, env: &Env, limit: u64) -> Result<Response, ContractError> {
+pub fn finish_auction(mut deps: DepsMut, env: &Env, limit: u64) -> Result<Response, ContractError> {
     let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
 
     if active_auction.status == ActiveAuctionStatus::Started
         && active_auction.end_block > env.block.height
         && !active_auction.available_amount.is_zero()
+        && !is_below_reserve_price(&active_auction, env.block.height)
     {
         return Err(ContractError::AuctionStillGoing);
     }
 
     let (start_from, mut total_sent_sold_token, mut total_sent_bought_token) = match active_auction
         .status
+        .clone()
     {
         ActiveAuctionStatus::CloseAuction(addr, total_sent_sold_token, total_sent_bought_token) => {
             Ok((addr, total_sent_sold_token, total_sent_bought_token))
@@ -247,11 +445,40 @@ pub fn finish_auction(deps: DepsMut, env: &Env, limit: u64) -> Result<Response,
 
     let config = AUCTION_CONFIG.load(deps.storage)?;
     let curr_auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+
+    // The protocol fee is taken once, before we start resolving the funds providers
+    let mut response = match active_auction.status {
+        ActiveAuctionStatus::Started | ActiveAuctionStatus::Finished => {
+            take_protocol_fee(deps.branch(), &config, curr_auction_id, &active_auction)?
+        }
+        _ => Response::default(),
+    };
+    let protocol_fee = AUCTION_PROTOCOL_FEES
+        .may_load(deps.storage, curr_auction_id)?
+        .unwrap_or_default();
+
+    let mut settlement = AuctionSettlement::from(&active_auction);
+    settlement.resolved_amount = settlement.resolved_amount.checked_sub(protocol_fee)?;
+
+    // If the price dropped below the reserve price, the unsold amount is not refunded
+    // to the funds providers, but added to their funds of the next auction
+    settlement.roll_over_unsold = is_below_reserve_price(&active_auction, env.block.height);
+
+    // An auction that is already partially resolved keeps being resolved in push mode
+    let is_resolving = matches!(active_auction.status, ActiveAuctionStatus::CloseAuction(..));
+    if !is_resolving
+        && SETTLEMENT_MODE.may_load(deps.storage)?.unwrap_or_default() == SettlementMode::Claim
+    {
+        return finish_auction_for_claims(deps, env, active_auction, settlement, response);
+    }
+
     let mut last_resolved = start_from.clone();
     let start_from = start_from.map(Bound::exclusive);
     let mut total_resolved = 0;
 
     let mut bank_msgs: Vec<CosmosMsg> = vec![];
+    let mut receipts: Vec<(Addr, ProviderReceipt)> = vec![];
+    let mut rolled_over: Vec<(Addr, Uint128)> = vec![];
 
     AUCTION_FUNDS
         .prefix(curr_auction_id)
@@ -265,52 +492,51 @@ pub fn finish_auction(deps: DepsMut, env: &Env, limit: u64) -> Result<Response,
         .try_for_each(|res| -> Result<(), ContractError> {
             total_resolved += 1;
             let (addr, amount) = res?;
-            let mut send_funds: Vec<Coin> = vec![];
 
-            if active_auction.resolved_amount.is_zero() {
-                // We didn't sell anything, so refund
-                send_funds.push(coin(amount.u128(), &config.pair.0));
-                total_sent_sold_token += amount;
-            } else {
-                // We sold something, calculate only what we sold
-                let perc_of_total = Decimal::from_atomics(amount, 0)?
-                    / Decimal::from_atomics(active_auction.total_amount, 0)?;
-                let to_send_amount =
-                    Decimal::from_atomics(active_auction.resolved_amount, 0)? * perc_of_total;
-
-                // TODO: Verify this is correct
-                let to_send_amount = if to_send_amount - to_send_amount.floor()
-                    >= Decimal::bps(CLOSEST_TO_ONE_POSSIBLE)
-                {
-                    to_send_amount.to_uint_ceil()
-                } else {
-                    to_send_amount.to_uint_floor()
-                };
-
-                total_sent_bought_token += to_send_amount;
-                send_funds.push(coin(to_send_amount.u128(), &config.pair.1));
-
-                // If we still have available amount, we refund based on the perc from total provided
-                if !active_auction.available_amount.is_zero() {
-                    let to_send_amount =
-                        (Decimal::from_atomics(active_auction.available_amount, 0)?
-                            * perc_of_total)
-                            .to_uint_floor();
-                    total_sent_sold_token += to_send_amount;
-                    send_funds.push(coin(to_send_amount.u128(), &config.pair.0));
-                }
-            }
+            let (unsold_amount, bought_amount) = calc_provider_share(amount, &settlement)?;
+            total_sent_sold_token += unsold_amount;
+            total_sent_bought_token += bought_amount;
 
-            let send_msg = BankMsg::Send {
-                to_address: addr.to_string(),
-                amount: send_funds,
+            let refund_amount = if settlement.roll_over_unsold {
+                rolled_over.push((addr.clone(), unsold_amount));
+                Uint128::zero()
+            } else {
+                unsold_amount
             };
-            bank_msgs.push(send_msg.into());
+
+            bank_msgs.extend(provider_send_msgs(
+                &addr,
+                &config,
+                refund_amount,
+                bought_amount,
+            )?);
+
+            receipts.push((
+                addr.clone(),
+                ProviderReceipt {
+                    sent: amount,
+                    received: bought_amount,
+                    leftover: unsold_amount,
+                },
+            ));
 
             last_resolved = Some(addr);
             Ok(())
         })?;
 
+    let save_dust_providers = saves_dust_providers(deps.storage)?;
+    for (addr, receipt) in receipts {
+        if save_dust_providers {
+            save_dust_provider(deps.storage, curr_auction_id, &addr, receipt.sent)?;
+        }
+        PROVIDER_RECEIPTS.save(deps.storage, (addr, curr_auction_id), &receipt)?;
+    }
+    for (addr, amount) in rolled_over {
+        if !amount.is_zero() {
+            add_next_auction_funds(deps.storage, &addr, amount)?;
+        }
+    }
+
     // If we looped over less than our limit, it means we resolved everything
     let (status, price, is_closed) = if total_resolved < limit {
         // calculate if we have leftover from rounding and add it to the next auction
@@ -319,37 +545,14 @@ pub fn finish_auction(deps: DepsMut, env: &Env, limit: u64) -> Result<Response,
             .checked_sub(total_sent_sold_token)?;
         let leftover_bought_token = active_auction
             .resolved_amount
-            .checked_sub(total_sent_bought_token)?;
-
-        active_auction.leftovers[0] = leftover_sold_token;
-        active_auction.leftovers[1] = leftover_bought_token;
+            .checked_sub(total_sent_bought_token)?
+            .checked_sub(protocol_fee)?;
 
-        // Update twap price if we have something sold
-        let sold_amount = active_auction
-            .total_amount
-            .checked_sub(active_auction.available_amount)?;
+        // Leftovers might already hold the claim remainders of a previous auction
+        active_auction.leftovers[0] += leftover_sold_token;
+        active_auction.leftovers[1] += leftover_bought_token;
 
-        let price = if !active_auction.total_amount.is_zero() && !sold_amount.is_zero() {
-            let avg_price = Decimal::from_atomics(active_auction.resolved_amount, 0)?
-                .checked_div(Decimal::from_atomics(sold_amount, 0)?)?;
-
-            let mut prices = TWAP_PRICES.load(deps.storage)?;
-
-            // if we have the needed amount of prices already, remove the last one first
-            if prices.len() >= TWAP_PRICE_MAX_LEN as usize {
-                prices.pop_back();
-            }
-
-            prices.push_front(Price {
-                price: avg_price,
-                time: env.block.time,
-            });
-
-            TWAP_PRICES.save(deps.storage, &prices)?;
-            avg_price.to_string()
-        } else {
-            "0".to_string()
-        };
+        let price = update_twap_prices(deps.branch(), env, &active_auction)?;
 
         (ActiveAuctionStatus::AuctionClosed, price, true)
     } else {
@@ -371,12 +574,215 @@ pub fn finish_auction(deps: DepsMut, env: &Env, limit: u64) -> Result<Response,
         auction_id: curr_auction_id,
         is_closed,
         price,
-        accounts: bank_msgs.len() as u64,
+        accounts: total_resolved,
+    };
+
+    response = response.add_event(event.into()).add_messages(bank_msgs);
+
+    Ok(response)
+}
+
+/// Take the protocol fee from the resolved amount of pair.1 and send it to the treasury
+fn take_protocol_fee(
+    deps: DepsMut,
+    config: &AuctionConfig,
+    auction_id: u64,
+    active_auction: &ActiveAuction,
+) -> Result<Response, ContractError> {
+    let protocol_fee = match &config.protocol_fee {
+        Some(protocol_fee) => protocol_fee,
+        None => return Ok(Response::default()),
+    };
+
+    let fee_amount = active_auction.resolved_amount * Decimal::bps(protocol_fee.bps);
+
+    if fee_amount.is_zero() {
+        return Ok(Response::default());
+    }
+
+    AUCTION_PROTOCOL_FEES.save(deps.storage, auction_id, &fee_amount)?;
+    let accrued = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
+    ACCRUED_FEES.save(deps.storage, &(accrued + fee_amount))?;
+
+    let fee = coin(fee_amount.u128(), &config.pair.1);
+
+    let event = ValenceEvent::AuctionProtocolFee {
+        auction_id,
+        fee: fee.clone(),
+        treasury: protocol_fee.treasury.to_string(),
     };
 
     Ok(Response::default()
         .add_event(event.into())
-        .add_messages(bank_msgs))
+        .add_messages(config.send_funds_msgs(&protocol_fee.treasury, vec![fee])?))
+}
+
+/// Close the auction without sending funds to the funds providers,
+/// the results of the auction are saved so providers can claim their share later.
+fn finish_auction_for_claims(
+    mut deps: DepsMut,
+    env: &Env,
+    mut active_auction: ActiveAuction,
+    mut settlement: AuctionSettlement,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let curr_auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+
+    // Leftovers of the previous auction are part of the total amount, but have no provider to claim them
+    settlement.unclaimed_funds = AUCTION_FUNDS_SUM
+        .may_load(deps.storage, curr_auction_id)?
+        .unwrap_or_default();
+
+    AUCTION_SETTLEMENTS.save(deps.storage, curr_auction_id, &settlement)?;
+    if !settlement.unclaimed_funds.is_zero() {
+        UNCLAIMED_SETTLEMENTS.save(deps.storage, curr_auction_id, &Empty {})?;
+    }
+
+    let price = update_twap_prices(deps.branch(), env, &active_auction)?;
+
+    // Rounding leftovers are only known once everyone claimed,
+    // so they are added to the leftovers by the last claim.
+    // No provider will claim, so everything is rolled into the next auction now
+    if settlement.unclaimed_funds.is_zero() {
+        active_auction.leftovers[0] += settlement.available_amount;
+        active_auction.leftovers[1] += settlement.resolved_amount;
+    }
+    active_auction.status = ActiveAuctionStatus::AuctionClosed;
+    ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
+
+    let event = ValenceEvent::AuctionClose {
+        auction_id: curr_auction_id,
+        is_closed: true,
+        price,
+        accounts: 0,
+    };
+
+    Ok(response.add_event(event.into()))
+}
+
+/// Claim the share of a funds provider from an auction that was finished in claim mode
+pub fn claim(deps: DepsMut, auction_id: u64, provider: Addr) -> Result<Response, ContractError> {
+    let mut settlement = AUCTION_SETTLEMENTS
+        .load(deps.storage, auction_id)
+        .map_err(|_| ContractError::NoClaimableAuction(auction_id))?;
+
+    // Provider was already resolved, either by a claim or by a push settlement
+    if PROVIDER_RECEIPTS.has(deps.storage, (provider.clone(), auction_id)) {
+        return Err(ContractError::NothingToClaim);
+    }
+
+    let amount = AUCTION_FUNDS
+        .may_load(deps.storage, (auction_id, provider.clone()))?
+        .ok_or(ContractError::NothingToClaim)?;
+
+    let (unsold_amount, bought_amount) = calc_provider_share(amount, &settlement)?;
+
+    settlement.unclaimed_funds = settlement.unclaimed_funds.checked_sub(amount)?;
+    settlement.claimed[0] += unsold_amount;
+    settlement.claimed[1] += bought_amount;
+
+    // The last claim rolls the rounding remainders into the leftovers of the next auction
+    if settlement.unclaimed_funds.is_zero() {
+        let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
+        active_auction.leftovers[0] += settlement
+            .available_amount
+            .checked_sub(settlement.claimed[0])?;
+        active_auction.leftovers[1] += settlement
+            .resolved_amount
+            .checked_sub(settlement.claimed[1])?;
+        ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
+        UNCLAIMED_SETTLEMENTS.remove(deps.storage, auction_id);
+    }
+
+    AUCTION_SETTLEMENTS.save(deps.storage, auction_id, &settlement)?;
+    AUCTION_FUNDS.remove(deps.storage, (auction_id, provider.clone()));
+
+    let refund_amount = if settlement.roll_over_unsold && !unsold_amount.is_zero() {
+        add_next_auction_funds(deps.storage, &provider, unsold_amount)?;
+        Uint128::zero()
+    } else {
+        unsold_amount
+    };
+    // Only the dust of the current auction can be sent to its providers
+    if auction_id == AUCTION_IDS.load(deps.storage)?.curr && saves_dust_providers(deps.storage)? {
+        save_dust_provider(deps.storage, auction_id, &provider, amount)?;
+    }
+    PROVIDER_RECEIPTS.save(
+        deps.storage,
+        (provider.clone(), auction_id),
+        &ProviderReceipt {
+            sent: amount,
+            received: bought_amount,
+            leftover: unsold_amount,
+        },
+    )?;
+
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+
+    let event = ValenceEvent::AuctionClaim {
+        auction_id,
+        address: provider.to_string(),
+        bought_amount,
+        unsold_amount,
+    };
+
+    Ok(Response::default()
+        .add_event(event.into())
+        .add_messages(provider_send_msgs(
+            &provider,
+            &config,
+            refund_amount,
+            bought_amount,
+        )?))
+}
+
+/// Build the messages to send the provider's share of the auction, if there is anything to send
+fn provider_send_msgs(
+    provider: &Addr,
+    config: &AuctionConfig,
+    unsold_amount: Uint128,
+    bought_amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    config.send_funds_msgs(
+        provider,
+        vec![
+            coin(bought_amount.u128(), &config.pair.1),
+            coin(unsold_amount.u128(), &config.pair.0),
+        ],
+    )
+}
+
+/// Update twap price if we have something sold, returns the avg price of the auction
+fn update_twap_prices(
+    deps: DepsMut,
+    env: &Env,
+    active_auction: &ActiveAuction,
+) -> Result<String, ContractError> {
+    let sold_amount = active_auction
+        .total_amount
+        .checked_sub(active_auction.available_amount)?;
+
+    if active_auction.total_amount.is_zero() || sold_amount.is_zero() {
+        return Ok("0".to_string());
+    }
+
+    let avg_price = Decimal::from_atomics(active_auction.resolved_amount, 0)?
+        .checked_div(Decimal::from_atomics(sold_amount, 0)?)?;
+
+    let mut prices = TWAP_PRICES.load(deps.storage)?;
+
+    // if we have the needed amount of prices already, remove the last one first
+    if prices.len() >= TWAP_PRICE_MAX_LEN as usize {
+        prices.pop_back();
+    }
+
+    prices.push_front(Price {
+        price: avg_price,
+        time: env.block.time,
+    });
+
+    TWAP_PRICES.save(deps.storage, &prices)?;
+    Ok(avg_price.to_string())
 }
 
 pub fn clean_auction(deps: DepsMut) -> Result<Response, ContractError> {
@@ -389,12 +795,293 @@ pub fn clean_auction(deps: DepsMut) -> Result<Response, ContractError> {
 
     let curr_auction_id = AUCTION_IDS.load(deps.storage)?.curr;
 
-    // Clean the funds at the id of ended auction
-    AUCTION_FUNDS
-        .prefix(curr_auction_id)
-        .clear(deps.storage, None);
+    // Clean the funds at the id of ended auction,
+    // unless providers still need to claim their share
+    if !AUCTION_SETTLEMENTS.has(deps.storage, curr_auction_id) {
+        AUCTION_FUNDS
+            .prefix(curr_auction_id)
+            .clear(deps.storage, None);
+    }
     // Clean the funds sum
     AUCTION_FUNDS_SUM.remove(deps.storage, curr_auction_id);
 
+    // Clean the providers of the ended auction, unless its dust can still be sent to them
+    let sweep_auction_id = DUST_SWEEP
+        .may_load(deps.storage)?
+        .map(|sweep| sweep.auction_id);
+    let has_dust = !SWEPT_DUST.has(deps.storage, curr_auction_id)
+        && !get_dust(
+            deps.as_ref(),
+            &AUCTION_CONFIG.load(deps.storage)?.pair,
+            &active_auction.leftovers,
+        )?
+        .is_empty();
+    let has_refund = DEPRECATED.may_load(deps.storage)?.unwrap_or_default()
+        && active_auction
+            .leftovers
+            .iter()
+            .any(|leftover| !leftover.is_zero());
+
+    if sweep_auction_id != Some(curr_auction_id)
+        && !has_dust
+        && !has_refund
+        && !AUCTION_SETTLEMENTS.has(deps.storage, curr_auction_id)
+    {
+        clear_dust_providers(deps.storage, curr_auction_id);
+    }
+    // The dust of the previous auction was rolled into the ended auction
+    if let Some(prev_auction_id) = curr_auction_id.checked_sub(1) {
+        if sweep_auction_id != Some(prev_auction_id) {
+            clear_dust_providers(deps.storage, prev_auction_id);
+        }
+    }
+
     Ok(Response::default())
 }
+
+pub fn sweep_dust(deps: DepsMut, limit: Option<u64>) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_DUST_SWEEP_LIMIT);
+
+    // Finish redistributing the dust of the previous sweep first
+    if let Some(sweep) = DUST_SWEEP.may_load(deps.storage)? {
+        return redistribute_dust(deps, sweep, limit, Response::default());
+    }
+
+    let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
+
+    match active_auction.status {
+        ActiveAuctionStatus::AuctionClosed => Ok::<_, ContractError>(()),
+        _ => return Err(ContractError::AuctionNotClosed),
+    }?;
+
+    let curr_auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+
+    if SWEPT_DUST.has(deps.storage, curr_auction_id) {
+        return Err(ContractError::NoDustToSweep);
+    }
+
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+    let dust = get_dust(deps.as_ref(), &config.pair, &active_auction.leftovers)?;
+
+    if dust.is_empty() {
+        return Err(ContractError::NoDustToSweep);
+    }
+
+    let destination = match DUST_DESTINATION.may_load(deps.storage)? {
+        Some(destination) => Ok(destination),
+        None => Err(ContractError::NoDustDestination),
+    }?;
+
+    // Swept dust is no longer rolled into the next auction
+    for dust_coin in dust.iter() {
+        if dust_coin.denom == config.pair.0 {
+            active_auction.leftovers[0] = Uint128::zero();
+        } else {
+            active_auction.leftovers[1] = Uint128::zero();
+        }
+    }
+    ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
+    SWEPT_DUST.save(deps.storage, curr_auction_id, &dust)?;
+
+    let event = ValenceEvent::AuctionSweepDust {
+        auction_id: curr_auction_id,
+        dust: dust.clone(),
+        destination: destination.clone(),
+    };
+    let response = Response::default().add_event(event.into());
+
+    match destination {
+        DustDestination::Recipient(recipient) => {
+            Ok(response.add_messages(config.send_funds_msgs(&recipient, dust)?))
+        }
+        DustDestination::LatestProviders => {
+            let providers_total = DUST_PROVIDERS_TOTAL
+                .may_load(deps.storage, curr_auction_id)?
+                .unwrap_or_default();
+
+            if providers_total.total.is_zero() {
+                return Err(ContractError::NoProvidersForDust);
+            }
+
+            let sweep = DustSweep {
+                auction_id: curr_auction_id,
+                sent: vec![Uint128::zero(); dust.len()],
+                dust,
+                last_provider: None,
+            };
+
+            redistribute_dust(deps, sweep, limit, response)
+        }
+    }
+}
+
+/// Refund all the leftovers of the closed auction of a deprecated pair to the latest providers,
+/// the leftovers can't be rolled into a next auction, and only dust can be swept
+pub fn refund_leftovers(deps: DepsMut, limit: Option<u64>) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_DUST_SWEEP_LIMIT);
+
+    if !DEPRECATED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::AuctionNotDeprecated);
+    }
+
+    // Finish redistributing the dust of the previous sweep first
+    if let Some(sweep) = DUST_SWEEP.may_load(deps.storage)? {
+        return redistribute_dust(deps, sweep, limit, Response::default());
+    }
+
+    let mut active_auction = ACTIVE_AUCTION.load(deps.storage)?;
+
+    match active_auction.status {
+        ActiveAuctionStatus::AuctionClosed => Ok::<_, ContractError>(()),
+        _ => return Err(ContractError::AuctionNotClosed),
+    }?;
+
+    let curr_auction_id = AUCTION_IDS.load(deps.storage)?.curr;
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+
+    let leftovers = [&config.pair.0, &config.pair.1]
+        .into_iter()
+        .zip(active_auction.leftovers.iter())
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(denom, amount)| coin(amount.u128(), denom))
+        .collect::<Vec<Coin>>();
+
+    if leftovers.is_empty() {
+        return Err(ContractError::NoLeftoversToRefund);
+    }
+
+    let providers_total = DUST_PROVIDERS_TOTAL
+        .may_load(deps.storage, curr_auction_id)?
+        .unwrap_or_default();
+
+    if providers_total.total.is_zero() {
+        return Err(ContractError::NoProvidersForDust);
+    }
+
+    active_auction.leftovers = [Uint128::zero(); 2];
+    ACTIVE_AUCTION.save(deps.storage, &active_auction)?;
+
+    let event = ValenceEvent::AuctionRefundLeftovers {
+        auction_id: curr_auction_id,
+        leftovers: leftovers.clone(),
+    };
+
+    let sweep = DustSweep {
+        auction_id: curr_auction_id,
+        sent: vec![Uint128::zero(); leftovers.len()],
+        dust: leftovers,
+        last_provider: None,
+    };
+
+    redistribute_dust(
+        deps,
+        sweep,
+        limit,
+        Response::default().add_event(event.into()),
+    )
+}
+
+/// Send the next `limit` providers their share of the dust by the funds they sent.
+/// The largest provider is paid once all the others were paid, and gets the rounding remainder
+fn redistribute_dust(
+    deps: DepsMut,
+    mut sweep: DustSweep,
+    limit: u64,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let config = AUCTION_CONFIG.load(deps.storage)?;
+    let providers_total = DUST_PROVIDERS_TOTAL.load(deps.storage, sweep.auction_id)?;
+    let largest = providers_total.largest.map(|(addr, _)| addr);
+
+    let providers = DUST_PROVIDERS
+        .prefix(sweep.auction_id)
+        .range(
+            deps.storage,
+            sweep.last_provider.clone().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+
+    let mut send_msgs: Vec<CosmosMsg> = vec![];
+    for (provider, amount) in providers.iter() {
+        DUST_PROVIDERS.remove(deps.storage, (sweep.auction_id, provider.clone()));
+
+        if Some(provider) == largest.as_ref() {
+            continue;
+        }
+
+        let mut share: Vec<Coin> = vec![];
+        for (dust_coin, sent) in sweep.dust.iter().zip(sweep.sent.iter_mut()) {
+            let amount = dust_coin
+                .amount
+                .multiply_ratio(*amount, providers_total.total);
+            *sent += amount;
+            share.push(coin(amount.u128(), &dust_coin.denom));
+        }
+        send_msgs.extend(config.send_funds_msgs(provider, share)?);
+    }
+
+    if (providers.len() as u64) < limit {
+        if let Some(largest) = largest {
+            let remainder = sweep
+                .dust
+                .iter()
+                .zip(sweep.sent.iter())
+                .map(|(dust_coin, sent)| {
+                    Ok(coin(
+                        dust_coin.amount.checked_sub(*sent)?.u128(),
+                        &dust_coin.denom,
+                    ))
+                })
+                .collect::<Result<Vec<Coin>, ContractError>>()?;
+            send_msgs.extend(config.send_funds_msgs(&largest, remainder)?);
+        }
+
+        DUST_SWEEP.remove(deps.storage);
+        DUST_PROVIDERS_TOTAL.remove(deps.storage, sweep.auction_id);
+    } else {
+        sweep.last_provider = providers.last().map(|(provider, _)| provider.clone());
+        DUST_SWEEP.save(deps.storage, &sweep)?;
+    }
+
+    Ok(response.add_messages(send_msgs))
+}
+
+/// The providers are only needed when the dust is redistributed to them,
+/// or when the leftovers of a deprecated auction are refunded to them
+fn saves_dust_providers(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(DEPRECATED.may_load(storage)?.unwrap_or_default()
+        || DUST_DESTINATION.may_load(storage)? == Some(DustDestination::LatestProviders))
+}
+
+fn clear_dust_providers(storage: &mut dyn Storage, auction_id: u64) {
+    DUST_PROVIDERS.prefix(auction_id).clear(storage, None);
+    DUST_PROVIDERS_TOTAL.remove(storage, auction_id);
+}
+
+/// Save the funds the provider sent to the auction, so the dust of the auction
+/// can be redistributed to the provider after the funds of the auction are cleaned
+fn save_dust_provider(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    provider: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    DUST_PROVIDERS.save(storage, (auction_id, provider.clone()), &amount)?;
+
+    let mut providers_total = DUST_PROVIDERS_TOTAL
+        .may_load(storage, auction_id)?
+        .unwrap_or_default();
+    providers_total.total += amount;
+    if providers_total
+        .largest
+        .as_ref()
+        .map_or(true, |(_, largest)| amount >= *largest)
+    {
+        providers_total.largest = Some((provider.clone(), amount));
+    }
+
+    DUST_PROVIDERS_TOTAL.save(storage, auction_id, &providers_total)
+}
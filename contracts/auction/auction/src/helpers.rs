@@ -1,7 +1,15 @@
-use auction_package::helpers::ChainHaltConfig;
-use cosmwasm_std::{BlockInfo, Decimal, Env, Uint128};
+use auction_package::{
+    helpers::ChainHaltConfig,
+    states::{ADMIN, MIN_AUCTION_AMOUNT},
+    BidderGate, Pair, CLOSEST_TO_ONE_POSSIBLE,
+};
+use cosmwasm_std::{coin, Addr, BlockInfo, Coin, Decimal, Deps, Env, StdResult, Uint128};
+use cw4::Cw4Contract;
 
-use crate::state::ActiveAuction;
+use crate::{
+    error::ContractError,
+    state::{ActiveAuction, AuctionSettlement, BIDDER_ALLOWLIST, BIDDER_GATE},
+};
 
 pub fn calc_price(terms: &ActiveAuction, curr_height: u64) -> Decimal {
     let block_diff = Decimal::from_atomics(terms.end_block - terms.start_block, 0).unwrap();
@@ -13,6 +21,15 @@ pub fn calc_price(terms: &ActiveAuction, curr_height: u64) -> Decimal {
     terms.start_price - (price_per_block * block_passed)
 }
 
+/// Check if the auction price dropped below the reserve price of the auction
+pub fn is_below_reserve_price(terms: &ActiveAuction, curr_height: u64) -> bool {
+    let height = curr_height.clamp(terms.start_block, terms.end_block);
+
+    terms.reserve_price.map_or(false, |reserve_price| {
+        calc_price(terms, height) < reserve_price
+    })
+}
+
 /// Calc how much of pair.0 to send (bought amount) and how much pair.1 to refund (leftover)
 pub fn calc_buy_amount(price: Decimal, amount: Uint128) -> (Uint128, Uint128) {
     let amount = Decimal::from_atomics(amount, 0).unwrap();
@@ -24,16 +41,85 @@ pub fn calc_buy_amount(price: Decimal, amount: Uint128) -> (Uint128, Uint128) {
     (buy_floor.to_uint_floor(), leftover)
 }
 
+/// Calc the share of a funds provider from the results of the auction,
+/// returns how much of pair.0 (unsold) and pair.1 (bought) to send to the provider
+pub fn calc_provider_share(
+    amount: Uint128,
+    settlement: &AuctionSettlement,
+) -> Result<(Uint128, Uint128), ContractError> {
+    if settlement.resolved_amount.is_zero() && !settlement.available_amount.is_zero() {
+        // We didn't sell anything, so refund (unless the unsold amount was rolled over)
+        return Ok((amount, Uint128::zero()));
+    }
+
+    // We sold something, calculate only what we sold
+    let perc_of_total =
+        Decimal::from_atomics(amount, 0)? / Decimal::from_atomics(settlement.total_amount, 0)?;
+    let bought_amount = Decimal::from_atomics(settlement.resolved_amount, 0)? * perc_of_total;
+
+    // TODO: Verify this is correct
+    let bought_amount =
+        if bought_amount - bought_amount.floor() >= Decimal::bps(CLOSEST_TO_ONE_POSSIBLE) {
+            bought_amount.to_uint_ceil()
+        } else {
+            bought_amount.to_uint_floor()
+        };
+
+    // If we still have available amount, we refund based on the perc from total provided
+    let unsold_amount =
+        (Decimal::from_atomics(settlement.available_amount, 0)? * perc_of_total).to_uint_floor();
+
+    Ok((unsold_amount, bought_amount))
+}
+
 /// Check the diff of blocks and time to see if we had a chain halt of around our time_cap
 pub fn is_chain_halted(env: &Env, check_block: &BlockInfo, halt_config: &ChainHaltConfig) -> bool {
+    // Chain halted for at least 4 hours
+    chain_halt_duration(env, check_block, halt_config) as u128 > halt_config.cap
+}
+
+/// Seconds that passed since the check block and are not explained by the blocks produced since
+pub fn chain_halt_duration(
+    env: &Env,
+    check_block: &BlockInfo,
+    halt_config: &ChainHaltConfig,
+) -> u64 {
     let block_diff = Uint128::from(env.block.height - check_block.height);
     let time_diff = (env.block.time.seconds() - check_block.time.seconds()) as u128;
 
     let avg_time_passed = (block_diff * halt_config.block_avg).u128();
 
-    // Chain halted for at least 4 hours
-    if time_diff > avg_time_passed + halt_config.cap {
-        return true;
+    time_diff.saturating_sub(avg_time_passed) as u64
+}
+
+/// Check if the address is allowed to bid by the bidder gate of the auction
+pub fn is_allowed_bidder(deps: Deps, bidder: &Addr) -> StdResult<bool> {
+    match BIDDER_GATE.may_load(deps.storage)? {
+        None => Ok(true),
+        Some(BidderGate::Allowlist) => Ok(BIDDER_ALLOWLIST.has(deps.storage, bidder.clone())),
+        Some(BidderGate::Cw4Group(group)) => Ok(Cw4Contract(group)
+            .is_member(&deps.querier, bidder, None)?
+            .is_some()),
     }
-    false
+}
+
+/// Get the leftovers of the auction that are below the minimum send amount of their denom,
+/// a denom without a minimum amount has no dust
+pub fn get_dust(deps: Deps, pair: &Pair, leftovers: &[Uint128; 2]) -> StdResult<Vec<Coin>> {
+    let manager_addr = ADMIN.load(deps.storage)?;
+    let min_send_0 = MIN_AUCTION_AMOUNT
+        .query(&deps.querier, manager_addr.clone(), pair.0.clone())?
+        .unwrap_or_default()
+        .send;
+    let min_send_1 = MIN_AUCTION_AMOUNT
+        .query(&deps.querier, manager_addr, pair.1.clone())?
+        .unwrap_or_default()
+        .send;
+
+    Ok([(&pair.0, min_send_0), (&pair.1, min_send_1)]
+        .into_iter()
+        .zip(leftovers)
+        .filter(|((_, min_send), leftover)| !leftover.is_zero() && *leftover < min_send)
+        .map(|((denom, _), leftover)| coin(leftover.u128(), denom))
+        .collect())
 }
@@ -1,11 +1,13 @@
 use auction_package::{
     helpers::{AuctionConfig, ChainHaltConfig, GetPriceResponse},
-    AuctionStrategy, Pair, PriceFreshnessStrategy,
+    AuctionStrategy, BidderGate, DustDestination, Pair, PriceFreshnessStrategy, ProtocolFee,
+    SettlementMode,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, BlockInfo, Decimal, Uint128};
+use cosmwasm_std::{Addr, BlockInfo, Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
 
-use crate::state::{ActiveAuction, ActiveAuctionStatus};
+use crate::state::{ActiveAuction, ActiveAuctionStatus, AuctionFill, DustSweep, ProviderReceipt};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -13,6 +15,9 @@ pub struct InstantiateMsg {
     pub auction_strategy: AuctionStrategy,
     pub chain_halt_config: ChainHaltConfig,
     pub price_freshness_strategy: PriceFreshnessStrategy,
+    /// Denoms of the pair that are CW20 tokens, the denom of a CW20 token is its contract address
+    #[serde(default)]
+    pub cw20_denoms: Vec<String>,
 }
 
 #[cw_serde]
@@ -21,10 +26,17 @@ pub enum ExecuteMsg {
     AuctionFundsManager { sender: Addr },
     /// Send funds to be auctioned on the next auction
     AuctionFunds {},
+    /// Receive CW20 tokens, to be auctioned or to bid with, see `Cw20HookMsg`
+    Receive(Cw20ReceiveMsg),
     /// Withdraw funds from future auction, can only be called by the admin/auctions manager
     WithdrawFundsManager { sender: Addr },
     /// Withdraw funds from future auction
     WithdrawFunds {},
+    /// Reclaim funds from the current auction, can only be called by the admin/auctions manager
+    ReclaimFundsManager { sender: Addr },
+    /// Reclaim the share of the sender from the current auction,
+    /// allowed once the auction was paused or the chain halted for longer than the reclaim threshold
+    ReclaimFunds {},
     /// Bid on the current auction
     Bid {},
     /// Finish the current auction and send funds to the funds provider
@@ -33,16 +45,41 @@ pub enum ExecuteMsg {
     FinishAuction { limit: u64 },
     /// Message to clean finished auction unneeded storage
     CleanAfterAuction {},
+    /// Sweep the leftovers of the closed auction that are below the minimum send amount
+    /// to the dust destination, can be called by anyone.
+    /// Dust redistributed to the latest providers is sent to `limit` providers at a time,
+    /// the next calls continue sending it before a new sweep
+    SweepDust { limit: Option<u64> },
+    /// Claim the share of a funds provider from an auction that was finished in claim mode,
+    /// can be called by anyone on behalf of the provider, defaults to the sender
+    Claim {
+        auction_id: u64,
+        addr: Option<String>,
+    },
     /// Admin messages that can only be called by the auctions manager
     Admin(Box<AdminMsgs>),
 }
 
+/// Messages that can be sent with CW20 tokens
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Send pair.0 tokens to be auctioned on the next auction, can only be sent by the admin/auctions manager
+    AuctionFundsManager { sender: Addr },
+    /// Send pair.0 tokens to be auctioned on the next auction
+    AuctionFunds {},
+    /// Bid on the active auction with pair.1 tokens
+    Bid {},
+}
+
 #[cw_serde]
 pub struct NewAuctionParams {
     /// Optional start block, if not provided, it will start from the current block
     pub start_block: Option<u64>,
     /// When auction should end
     pub end_block: u64,
+    /// Optional minimum price, bids are rejected once the auction price drops below it,
+    /// and the unsold amount is added to the next auction
+    pub reserve_price: Option<Decimal>,
 }
 
 /// Admin messages that can only be called by the auctions manager
@@ -60,6 +97,27 @@ pub enum AdminMsgs {
     UpdateChainHaltConfig(ChainHaltConfig),
     /// Update the price freshness strategy
     UpdatePriceFreshnessStrategy(PriceFreshnessStrategy),
+    /// Update how funds are settled with the funds providers
+    UpdateSettlementMode(SettlementMode),
+    /// Update the fee taken from the resolved pair.1 of every auction, None to stop taking it
+    UpdateProtocolFee(Option<ProtocolFee>),
+    /// Update the seconds the auction must be paused or the chain halted
+    /// before funds can be reclaimed from the current auction
+    UpdateReclaimThreshold { seconds: u64 },
+    /// Update where dust leftovers are swept to, None to roll them into the next auction
+    UpdateDustDestination(Option<DustDestination>),
+    /// Update who can bid on the auction, None to allow anyone to bid
+    UpdateBidderGate(Option<BidderGate>),
+    /// Add and remove addresses from the bidder allowlist
+    UpdateBidderAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Stop accepting new funds, funds that were already sent can still be withdrawn or auctioned
+    Deprecate,
+    /// Refund all the leftovers of the closed auction to the latest providers, only when deprecated.
+    /// Sent to `limit` providers at a time, the next `SweepDust` calls continue the refund
+    RefundLeftovers { limit: Option<u64> },
 }
 
 #[cw_serde]
@@ -85,11 +143,77 @@ pub enum QueryMsg {
     #[returns(AuctionStrategy)]
     GetStrategy,
 
+    /// Get how funds are settled with the funds providers
+    #[returns(SettlementMode)]
+    GetSettlementMode,
+
+    /// Get the seconds the auction must be paused or the chain halted
+    /// before funds can be reclaimed from the current auction
+    #[returns(u64)]
+    GetReclaimThreshold,
+
+    /// Returns true if funds providers didn't claim their share of an auction settled in claim mode
+    #[returns(bool)]
+    HasUnclaimedSettlements,
+
+    /// Get where dust leftovers are swept to
+    #[returns(Option<DustDestination>)]
+    GetDustDestination,
+
+    /// Get the dust that is still being redistributed to the latest providers, if any
+    #[returns(Option<DustSweep>)]
+    GetDustSweep,
+
+    /// Get the dust of the auction id, the swept dust, or the dust that can be swept
+    /// if it's the latest closed auction
+    #[returns(Vec<Coin>)]
+    GetAuctionDust { auction_id: u64 },
+
+    /// Get who can bid on the auction, anyone can bid if not set
+    #[returns(Option<BidderGate>)]
+    GetBidderGate,
+
+    /// Get the addresses on the bidder allowlist
+    #[returns(Vec<Addr>)]
+    GetBidderAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns true if the address is allowed to bid on the auction
+    #[returns(bool)]
+    IsAllowedBidder { addr: String },
+
+    /// Get the total protocol fees of pair.1 that were sent to the treasury
+    #[returns(Coin)]
+    GetAccruedFees,
+
     #[returns(Addr)]
     GetAdmin,
 
+    /// Returns true if the auction is deprecated and doesn't accept new funds
+    #[returns(bool)]
+    IsDeprecated,
+
     #[returns(GetMmResponse)]
     GetMmData,
+
+    /// Get the fills of the given auction id, and summary stats of the auction
+    #[returns(GetAuctionFillsResponse)]
+    GetAuctionFills {
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Get the receipts of the given funds provider per auction id
+    /// (sent amount of pair.0, received amount of pair.1 and leftover of pair.0)
+    #[returns(Vec<(u64, ProviderReceipt)>)]
+    GetProviderHistory {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -111,3 +235,15 @@ pub struct GetMmResponse {
     pub price: Decimal,
     pub block: BlockInfo,
 }
+
+#[cw_serde]
+pub struct GetAuctionFillsResponse {
+    /// List of (fill index, fill)
+    pub fills: Vec<(u64, AuctionFill)>,
+    /// Volume weighted average price of all fills
+    pub vwap: Decimal,
+    /// Total amount of pair.1 that was paid by bidders
+    pub total_resolved: Uint128,
+    /// Percentage of pair.0 that was sold from the total amount
+    pub fill_ratio: Decimal,
+}
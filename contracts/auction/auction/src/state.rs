@@ -1,6 +1,9 @@
-use auction_package::{helpers::AuctionConfig, AuctionStrategy};
+pub use auction_package::ActiveAuctionStatus;
+use auction_package::{
+    helpers::AuctionConfig, AuctionStrategy, BidderGate, DustDestination, SettlementMode,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, BlockInfo, Decimal, Uint128};
+use cosmwasm_std::{Addr, BlockInfo, Coin, Decimal, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
 /// The config of any auction
@@ -12,6 +15,53 @@ pub const AUCTION_FUNDS: Map<(u64, Addr), Uint128> = Map::new("funds");
 /// Sum of the funds sent for auction for auction id
 pub const AUCTION_FUNDS_SUM: Map<u64, Uint128> = Map::new("funds_sum");
 
+/// Fills of bids per auction id and fill index
+pub const AUCTION_FILLS: Map<(u64, u64), AuctionFill> = Map::new("fills");
+/// Summary of the fills per auction id
+pub const AUCTION_FILLS_SUMMARY: Map<u64, AuctionFillsSummary> = Map::new("fills_summary");
+
+/// Receipts of funds providers per address and auction id, kept after the auction is cleaned
+pub const PROVIDER_RECEIPTS: Map<(Addr, u64), ProviderReceipt> = Map::new("provider_receipts");
+
+/// How funds are settled with the funds providers when an auction is finished
+pub const SETTLEMENT_MODE: Item<SettlementMode> = Item::new("settlement_mode");
+/// Results of auctions that were finished in claim mode, per auction id
+pub const AUCTION_SETTLEMENTS: Map<u64, AuctionSettlement> = Map::new("settlements");
+/// Auction ids of settlements that still have funds providers that didn't claim their share
+pub const UNCLAIMED_SETTLEMENTS: Map<u64, Empty> = Map::new("unclaimed_settlements");
+
+/// The protocol fee of pair.1 taken from the auction per auction id
+pub const AUCTION_PROTOCOL_FEES: Map<u64, Uint128> = Map::new("protocol_fees");
+/// The total protocol fees of pair.1 that were sent to the treasury
+pub const ACCRUED_FEES: Item<Uint128> = Item::new("accrued_fees");
+
+/// Restricts who can bid on the auction, anyone can bid if not set
+pub const BIDDER_GATE: Item<BidderGate> = Item::new("bidder_gate");
+/// Addresses that can bid when the bidder gate is set to the allowlist
+pub const BIDDER_ALLOWLIST: Map<Addr, Empty> = Map::new("bidder_allowlist");
+
+/// The time the auction was paused, removed once resumed
+pub const PAUSED_AT: Item<Timestamp> = Item::new("paused_at");
+/// Seconds the auction must be paused or the chain halted before funds providers
+/// can reclaim their funds from the current auction
+pub const RECLAIM_THRESHOLD: Item<u64> = Item::new("reclaim_threshold");
+
+/// Where dust leftovers are swept to, dust is rolled into the next auction if not set
+pub const DUST_DESTINATION: Item<DustDestination> = Item::new("dust_destination");
+/// The dust that was swept per auction id
+pub const SWEPT_DUST: Map<u64, Vec<Coin>> = Map::new("swept_dust");
+/// The funds providers of an auction and the funds they sent, saved when they are resolved
+/// and the dust is redistributed to them, or the auction is deprecated.
+/// Kept after the funds of the auction are cleaned only while its dust can still be sent
+pub const DUST_PROVIDERS: Map<(u64, Addr), Uint128> = Map::new("dust_providers");
+/// The total funds of the providers saved in `DUST_PROVIDERS` per auction id
+pub const DUST_PROVIDERS_TOTAL: Map<u64, DustProvidersTotal> = Map::new("dust_providers_total");
+/// The dust that is being redistributed to the latest providers, removed once all were paid
+pub const DUST_SWEEP: Item<DustSweep> = Item::new("dust_sweep");
+
+/// Set once the auction is deprecated, no new funds are accepted
+pub const DEPRECATED: Item<bool> = Item::new("deprecated");
+
 /// The active auction data
 pub const ACTIVE_AUCTION: Item<ActiveAuction> = Item::new("active_auction");
 /// The strategy we use when setting min and max prices for an auction
@@ -39,20 +89,8 @@ pub struct ActiveAuction {
     pub leftovers: [Uint128; 2],
     /// The last checked block for chain halts
     pub last_checked_block: BlockInfo,
-}
-
-#[cw_serde]
-pub enum ActiveAuctionStatus {
-    /// The auction started, and last resolved block height is (u64)
-    Started,
-    /// The auction is finished, waiting for the funds to be resolved
-    Finished,
-    /// Handle closing auction, addr of the last funds provider we resolved
-    /// and the total amounts of the pair we sent already
-    /// (provider, total_amount_pair.0, total_amount_pair.1)
-    CloseAuction(Option<Addr>, Uint128, Uint128),
-    /// The auction is closed
-    AuctionClosed,
+    /// The minimum price we are willing to sell at
+    pub reserve_price: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -60,3 +98,91 @@ pub struct AuctionIds {
     pub curr: u64,
     pub next: u64,
 }
+
+#[cw_serde]
+pub struct AuctionFill {
+    pub bidder: Addr,
+    pub block: u64,
+    pub price: Decimal,
+    /// The amount of pair.0 the bidder bought
+    pub bought_amount: Uint128,
+    /// The amount of pair.1 the bidder paid
+    pub paid_amount: Uint128,
+}
+
+/// Totals of the fills of an auction, the share of a provider that reclaimed the funds
+/// is removed from the totals
+#[cw_serde]
+#[derive(Default)]
+pub struct AuctionFillsSummary {
+    /// Amount of fills happened in the auction, also used as the index of the next fill
+    pub fills: u64,
+    /// The total funds of pair.0 that was sent to sell
+    pub total_amount: Uint128,
+    /// The total amount of pair.0 that was bought
+    pub total_bought: Uint128,
+    /// The total amount of pair.1 that was paid by bidders
+    pub total_paid: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct DustProvidersTotal {
+    /// The total funds of pair.0 the providers sent
+    pub total: Uint128,
+    /// The provider that sent the most funds, receives the rounding remainder of the dust
+    pub largest: Option<(Addr, Uint128)>,
+}
+
+#[cw_serde]
+pub struct DustSweep {
+    /// The auction the dust was swept from
+    pub auction_id: u64,
+    /// The dust that is redistributed
+    pub dust: Vec<Coin>,
+    /// The amount of every dust coin that was sent to the providers so far
+    pub sent: Vec<Uint128>,
+    /// The last provider that was paid, the next batch starts after it
+    pub last_provider: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ProviderReceipt {
+    /// The amount of pair.0 the provider sent to the auction
+    pub sent: Uint128,
+    /// The amount of pair.1 the provider received
+    pub received: Uint128,
+    /// The amount of pair.0 that wasn't sold and was sent back to the provider,
+    /// or added to the provider's next auction funds if the price dropped below the reserve price
+    pub leftover: Uint128,
+}
+
+#[cw_serde]
+pub struct AuctionSettlement {
+    /// The total funds of pair.0 that was sent to sell
+    pub total_amount: Uint128,
+    /// The amount of pair.0 that wasn't sold
+    pub available_amount: Uint128,
+    /// The received and resolved amount of pair.1
+    pub resolved_amount: Uint128,
+    /// The funds of pair.0 sent by providers that didn't claim their share yet
+    pub unclaimed_funds: Uint128,
+    /// The amounts of pair.0 and pair.1 that were already claimed
+    pub claimed: [Uint128; 2],
+    /// True if the price dropped below the reserve price,
+    /// the unsold amount is then added to the funds of the providers in the next auction
+    pub roll_over_unsold: bool,
+}
+
+impl From<&ActiveAuction> for AuctionSettlement {
+    fn from(auction: &ActiveAuction) -> Self {
+        Self {
+            total_amount: auction.total_amount,
+            available_amount: auction.available_amount,
+            resolved_amount: auction.resolved_amount,
+            unclaimed_funds: Uint128::zero(),
+            claimed: [Uint128::zero(), Uint128::zero()],
+            roll_over_unsold: false,
+        }
+    }
+}
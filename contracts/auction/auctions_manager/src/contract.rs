@@ -1,27 +1,53 @@
-use auction_package::helpers::{approve_admin_change, GetPriceResponse};
+use std::collections::VecDeque;
+
+use auction::helpers::{calc_price, is_below_reserve_price};
+use auction::msg::NewAuctionParams;
+use auction::state::{
+    ActiveAuction, DustSweep, ACTIVE_AUCTION, AUCTION_CONFIG, AUCTION_FUNDS_SUM, AUCTION_IDS,
+    SWEPT_DUST,
+};
+use auction_package::helpers::{
+    approve_admin_change, ActiveAuctionInfo, ActiveAuctionsResponse, AuctionSchedule,
+    GetPriceResponse, MigrationProgress,
+};
 use auction_package::msgs::AuctionsManagerQueryMsg;
 use auction_package::states::{
-    MinAmount, ADMIN, MIN_AUCTION_AMOUNT, MIN_AUCTION_AMOUNT_V0, ORACLE_ADDR, PAIRS,
+    MinAmount, ADMIN, DEPRECATED_PAIRS, MIN_AUCTION_AMOUNT, MIN_AUCTION_AMOUNT_V0, ORACLE_ADDR,
+    PAIRS,
 };
+use auction_package::{ActiveAuctionStatus, DustDestination, Pair};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult,
-    WasmMsg,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, Reply, Response, StdResult, SubMsg, SubMsgResult, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
 use cw_storage_plus::Bound;
 use cw_utils::{nonpayable, parse_reply_instantiate_data};
 use valence_package::event_indexing::ValenceEvent;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
-use crate::state::{AUCTION_CODE_ID, SERVER_ADDR};
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg};
+use crate::state::{
+    AuctionStep, PendingSteps, AUCTION_CODE_ID, AUCTION_SCHEDULES, CRANK_CURSOR, KEEPER_TIP,
+    LAST_SCHEDULED_OPEN, MIGRATION_PROGRESS, PENDING_STEPS, SERVER_ADDR, TEMPLATES,
+};
 
 const CONTRACT_NAME: &str = "crates.io:auctions-manager";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_AUCTION_REPLY_ID: u64 = 1;
+const AUCTION_STEP_REPLY_ID: u64 = 2;
+/// The default amount of schedules we go over in a single `OpenDueAuctions`
+const DEFAULT_OPEN_DUE_AUCTIONS_LIMIT: u32 = 10;
+/// The default amount of pairs we go over in a single `Crank`
+const DEFAULT_CRANK_LIMIT: u32 = 10;
+/// The default amount of auctions we migrate in a single `MigrateAllAuctions`
+const DEFAULT_MIGRATE_ALL_AUCTIONS_LIMIT: u32 = 10;
+/// The amount of funds providers we resolve when the crank finishes an auction
+const CRANK_FINISH_AUCTION_LIMIT: u64 = 10;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -53,7 +79,11 @@ pub fn execute(
     match msg {
         ExecuteMsg::AuctionFunds { pair } => {
             pair.verify()?;
-            let pair_addr = PAIRS.load(deps.storage, pair)?;
+            let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+            if DEPRECATED_PAIRS.has(deps.storage, pair.clone()) {
+                return Err(ContractError::AuctionDeprecated(pair));
+            }
 
             let msg = WasmMsg::Execute {
                 contract_addr: pair_addr.to_string(),
@@ -65,6 +95,39 @@ pub fn execute(
 
             Ok(Response::default().add_message(msg))
         }
+        ExecuteMsg::Receive(cw20_msg) => {
+            let hook_msg: Cw20HookMsg = from_json(&cw20_msg.msg)?;
+
+            match hook_msg {
+                Cw20HookMsg::AuctionFunds { pair } => {
+                    pair.verify()?;
+                    let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+                    if DEPRECATED_PAIRS.has(deps.storage, pair.clone()) {
+                        return Err(ContractError::AuctionDeprecated(pair));
+                    }
+
+                    // The sender is the token contract
+                    if info.sender != pair.0 {
+                        return Err(ContractError::InvalidCw20Token(info.sender.to_string()));
+                    }
+
+                    let msg = WasmMsg::Execute {
+                        contract_addr: info.sender.to_string(),
+                        msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                            contract: pair_addr.to_string(),
+                            amount: cw20_msg.amount,
+                            msg: to_json_binary(&auction::msg::Cw20HookMsg::AuctionFundsManager {
+                                sender: deps.api.addr_validate(&cw20_msg.sender)?,
+                            })?,
+                        })?,
+                        funds: vec![],
+                    };
+
+                    Ok(Response::default().add_message(msg))
+                }
+            }
+        }
         ExecuteMsg::WithdrawFunds { pair } => {
             nonpayable(&info)?;
             pair.verify()?;
@@ -80,6 +143,21 @@ pub fn execute(
 
             Ok(Response::default().add_message(msg))
         }
+        ExecuteMsg::ReclaimFunds { pair } => {
+            nonpayable(&info)?;
+            pair.verify()?;
+            let pair_addr = PAIRS.load(deps.storage, pair)?;
+
+            let msg = WasmMsg::Execute {
+                contract_addr: pair_addr.to_string(),
+                msg: to_json_binary(&auction::msg::ExecuteMsg::ReclaimFundsManager {
+                    sender: info.sender,
+                })?,
+                funds: vec![],
+            };
+
+            Ok(Response::default().add_message(msg))
+        }
         ExecuteMsg::FinishAuction { pair, limit } => {
             nonpayable(&info)?;
             pair.verify()?;
@@ -93,6 +171,14 @@ pub fn execute(
 
             Ok(Response::default().add_message(msg))
         }
+        ExecuteMsg::OpenDueAuctions { start_after, limit } => {
+            nonpayable(&info)?;
+            open_due_auctions(deps, &env, &info, start_after, limit)
+        }
+        ExecuteMsg::Crank { pairs, limit } => {
+            nonpayable(&info)?;
+            crank(deps, &env, &info, pairs, limit)
+        }
         ExecuteMsg::Admin(admin_msg) => admin::handle_msg(deps, env, info, *admin_msg),
         ExecuteMsg::Server(server_msg) => server::handle_msg(deps, env, info, server_msg),
         ExecuteMsg::ApproveAdminChange {} => {
@@ -102,6 +188,427 @@ pub fn execute(
     }
 }
 
+/// Open the auctions that are due by their schedule, and tip the sender for every opened auction
+fn open_due_auctions(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    start_after: Option<Pair>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_OPEN_DUE_AUCTIONS_LIMIT) as usize;
+
+    let schedules = AUCTION_SCHEDULES
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut steps = PendingSteps {
+        keeper: info.sender.clone(),
+        steps: VecDeque::new(),
+    };
+    let mut sub_msgs: Vec<SubMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    for (pair, schedule) in schedules {
+        // A pair we fail to check doesn't fail the other pairs
+        let res = due_open_msg(deps.as_ref(), env, &pair, &schedule);
+
+        match res {
+            Ok(Some(msg)) => {
+                sub_msgs.push(SubMsg::reply_always(msg, AUCTION_STEP_REPLY_ID));
+                steps.steps.push_back((pair, AuctionStep::Open));
+            }
+            Ok(None) => {}
+            Err(err) => events.push(step_failed_event(pair, "check", err.to_string())),
+        }
+    }
+
+    if steps.steps.is_empty() {
+        return Err(ContractError::NoDueAuctions);
+    }
+
+    let event = ValenceEvent::AuctionManagerOpenDueAuctions {
+        pairs: steps.steps.iter().map(|(pair, _)| pair.clone()).collect(),
+    };
+    PENDING_STEPS.save(deps.storage, &steps)?;
+
+    Ok(Response::default()
+        .add_submessages(sub_msgs)
+        .add_events(events)
+        .add_event(event.into()))
+}
+
+/// Get the message to open the auction of the pair, if it's due by its schedule
+fn due_open_msg(
+    deps: Deps,
+    env: &Env,
+    pair: &Pair,
+    schedule: &AuctionSchedule,
+) -> Result<Option<WasmMsg>, ContractError> {
+    let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+    let active_auction = ACTIVE_AUCTION.query(&deps.querier, pair_addr.clone())?;
+
+    if !is_auction_due(deps, env, pair, &pair_addr, schedule, &active_auction)? {
+        return Ok(None);
+    }
+
+    Ok(Some(scheduled_open_msg(env, &pair_addr, schedule)?))
+}
+
+/// Drive the auctions of the pairs forward: finish ended auctions, sweep the dust and clean
+/// closed auctions, and open auctions that are due by their schedule
+fn crank(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    pairs: Option<Vec<Pair>>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_CRANK_LIMIT) as usize;
+
+    if limit == 0 {
+        return Err(ContractError::LimitIsZero);
+    }
+
+    let pairs = match pairs {
+        Some(pairs) => pairs,
+        None => {
+            // Continue from the last pair the previous crank went over
+            let start_after = CRANK_CURSOR.may_load(deps.storage)?;
+            let mut pairs = PAIRS
+                .keys(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit + 1)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            // If we got more than the limit, the next crank continues from the last pair
+            if pairs.len() > limit {
+                pairs.truncate(limit);
+                CRANK_CURSOR.save(deps.storage, &pairs[limit - 1])?;
+            } else {
+                CRANK_CURSOR.remove(deps.storage);
+            }
+
+            pairs
+        }
+    };
+
+    let mut sub_msgs: Vec<SubMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+    let mut steps = PendingSteps {
+        keeper: info.sender.clone(),
+        steps: VecDeque::new(),
+    };
+    let mut finished: Vec<Pair> = vec![];
+    let mut swept: Vec<Pair> = vec![];
+    let mut cleaned: Vec<Pair> = vec![];
+    let mut opened: Vec<Pair> = vec![];
+
+    for pair in pairs {
+        // A pair we fail to check doesn't fail the other pairs, and the cursor moves past it
+        let pair_steps = match crank_steps(deps.as_ref(), env, &pair) {
+            Ok(pair_steps) => pair_steps,
+            Err(err) => {
+                events.push(step_failed_event(pair, "check", err.to_string()));
+                continue;
+            }
+        };
+
+        for (msg, step) in pair_steps {
+            match step {
+                AuctionStep::Finish => finished.push(pair.clone()),
+                AuctionStep::SweepDust => swept.push(pair.clone()),
+                AuctionStep::Clean => cleaned.push(pair.clone()),
+                AuctionStep::Open => opened.push(pair.clone()),
+            }
+
+            sub_msgs.push(SubMsg::reply_always(msg, AUCTION_STEP_REPLY_ID));
+            steps.steps.push_back((pair.clone(), step));
+        }
+    }
+
+    if !steps.steps.is_empty() {
+        PENDING_STEPS.save(deps.storage, &steps)?;
+    }
+
+    let event = ValenceEvent::AuctionManagerCrank {
+        finished,
+        swept,
+        cleaned,
+        opened,
+    };
+
+    Ok(Response::default()
+        .add_submessages(sub_msgs)
+        .add_events(events)
+        .add_event(event.into()))
+}
+
+/// Get the steps that move the auction of the pair forward, in the order they should be sent
+fn crank_steps(
+    deps: Deps,
+    env: &Env,
+    pair: &Pair,
+) -> Result<Vec<(WasmMsg, AuctionStep)>, ContractError> {
+    let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+    let active_auction = ACTIVE_AUCTION.query(&deps.querier, pair_addr.clone())?;
+    let mut steps: Vec<(WasmMsg, AuctionStep)> = vec![];
+
+    match active_auction.status {
+        ActiveAuctionStatus::Started => {
+            // Same as the auction, we can only finish it once it ended, sold out
+            // or its price dropped below the reserve price
+            if active_auction.end_block <= env.block.height
+                || active_auction.available_amount.is_zero()
+                || is_below_reserve_price(&active_auction, env.block.height)
+            {
+                steps.push((finish_auction_msg(&pair_addr)?, AuctionStep::Finish));
+            }
+        }
+        ActiveAuctionStatus::Finished | ActiveAuctionStatus::CloseAuction(..) => {
+            steps.push((finish_auction_msg(&pair_addr)?, AuctionStep::Finish));
+        }
+        ActiveAuctionStatus::AuctionClosed => {
+            let curr_auction_id = AUCTION_IDS.query(&deps.querier, pair_addr.clone())?.curr;
+
+            // Sweep the dust before the next auction is opened, the dust is rolled into it
+            if has_dust_to_sweep(deps, &pair_addr, curr_auction_id)? {
+                steps.push((
+                    WasmMsg::Execute {
+                        contract_addr: pair_addr.to_string(),
+                        msg: to_json_binary(&auction::msg::ExecuteMsg::SweepDust { limit: None })?,
+                        funds: vec![],
+                    },
+                    AuctionStep::SweepDust,
+                ));
+            }
+
+            // The funds of the closed auction were not cleaned yet
+            if AUCTION_FUNDS_SUM
+                .query(&deps.querier, pair_addr.clone(), curr_auction_id)?
+                .is_some()
+            {
+                steps.push((
+                    WasmMsg::Execute {
+                        contract_addr: pair_addr.to_string(),
+                        msg: to_json_binary(&auction::msg::ExecuteMsg::CleanAfterAuction {})?,
+                        funds: vec![],
+                    },
+                    AuctionStep::Clean,
+                ));
+            }
+
+            if let Some(schedule) = AUCTION_SCHEDULES.may_load(deps.storage, pair.clone())? {
+                if is_auction_due(deps, env, pair, &pair_addr, &schedule, &active_auction)? {
+                    steps.push((
+                        scheduled_open_msg(env, &pair_addr, &schedule)?,
+                        AuctionStep::Open,
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(steps)
+}
+
+/// The auction is still sending swept dust to the latest providers, or the closed auction
+/// has dust we didn't sweep yet and a destination to sweep it to
+fn has_dust_to_sweep(deps: Deps, pair_addr: &Addr, auction_id: u64) -> StdResult<bool> {
+    let sweep: Option<DustSweep> = deps
+        .querier
+        .query_wasm_smart(pair_addr, &auction::msg::QueryMsg::GetDustSweep)?;
+
+    if sweep.is_some() {
+        return Ok(true);
+    }
+
+    let destination: Option<DustDestination> = deps
+        .querier
+        .query_wasm_smart(pair_addr, &auction::msg::QueryMsg::GetDustDestination)?;
+
+    if destination.is_none()
+        || SWEPT_DUST
+            .query(&deps.querier, pair_addr.clone(), auction_id)?
+            .is_some()
+    {
+        return Ok(false);
+    }
+
+    let dust: Vec<Coin> = deps.querier.query_wasm_smart(
+        pair_addr,
+        &auction::msg::QueryMsg::GetAuctionDust { auction_id },
+    )?;
+
+    Ok(!dust.is_empty())
+}
+
+fn finish_auction_msg(pair_addr: &Addr) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: pair_addr.to_string(),
+        msg: to_json_binary(&auction::msg::ExecuteMsg::FinishAuction {
+            limit: CRANK_FINISH_AUCTION_LIMIT,
+        })?,
+        funds: vec![],
+    })
+}
+
+/// Get the message to open the auction for the duration of its schedule
+fn scheduled_open_msg(
+    env: &Env,
+    pair_addr: &Addr,
+    schedule: &AuctionSchedule,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: pair_addr.to_string(),
+        msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+            auction::msg::AdminMsgs::StartAuction(NewAuctionParams {
+                start_block: None,
+                end_block: env.block.height + schedule.duration_blocks,
+                reserve_price: schedule.reserve_price,
+            }),
+        )))?,
+        funds: vec![],
+    })
+}
+
+/// Get the tip for opening an auction, only if we have enough funds to pay it
+fn get_keeper_tip(deps: Deps, env: &Env) -> StdResult<Option<Coin>> {
+    match KEEPER_TIP.may_load(deps.storage)? {
+        Some(tip) => {
+            let balance = deps
+                .querier
+                .query_balance(env.contract.address.clone(), tip.denom.clone())?;
+
+            if balance.amount >= tip.amount {
+                Ok(Some(tip))
+            } else {
+                Ok(None)
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+fn step_failed_event(pair: Pair, step: impl ToString, error: String) -> Event {
+    ValenceEvent::AuctionManagerStepFailed {
+        pair,
+        step: step.to_string(),
+        error,
+    }
+    .into()
+}
+
+/// Handle the result of a step sent to an auction, the step is the first pending step.
+///
+/// A failed step only emits an event, so the other auctions of the batch are not reverted.
+/// A scheduled open is only recorded and tipped if the auction really started,
+/// and not when the funds were refunded because they are below the start auction minimum.
+fn handle_step_reply(
+    deps: DepsMut,
+    env: &Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let mut pending = PENDING_STEPS.load(deps.storage)?;
+    let (pair, step) = pending
+        .steps
+        .pop_front()
+        .ok_or(ContractError::UnknownReplyId(AUCTION_STEP_REPLY_ID))?;
+
+    if pending.steps.is_empty() {
+        PENDING_STEPS.remove(deps.storage);
+    } else {
+        PENDING_STEPS.save(deps.storage, &pending)?;
+    }
+
+    if let SubMsgResult::Err(error) = result {
+        return Ok(Response::default().add_event(step_failed_event(pair, step, error)));
+    }
+
+    match step {
+        AuctionStep::Finish | AuctionStep::SweepDust | AuctionStep::Clean => {
+            Ok(Response::default())
+        }
+        AuctionStep::Open => {
+            let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+            let active_auction = ACTIVE_AUCTION.query(&deps.querier, pair_addr)?;
+
+            if active_auction.status != ActiveAuctionStatus::Started {
+                return Ok(Response::default());
+            }
+
+            LAST_SCHEDULED_OPEN.save(deps.storage, pair.clone(), &env.block.height)?;
+
+            let tip = get_keeper_tip(deps.as_ref(), env)?;
+            let mut response = Response::default();
+
+            if let Some(tip) = &tip {
+                response = response.add_message(BankMsg::Send {
+                    to_address: pending.keeper.to_string(),
+                    amount: vec![tip.clone()],
+                });
+            }
+
+            let event = ValenceEvent::AuctionManagerOpenScheduledAuction { pair, tip };
+
+            Ok(response.add_event(event.into()))
+        }
+    }
+}
+
+/// An auction is due if it's closed and not paused, enough blocks passed since we last opened it,
+/// and enough funds were sent to the next auction to start it
+fn is_auction_due(
+    deps: Deps,
+    env: &Env,
+    pair: &Pair,
+    pair_addr: &Addr,
+    schedule: &AuctionSchedule,
+    active_auction: &ActiveAuction,
+) -> Result<bool, ContractError> {
+    if let Some(last_open) = LAST_SCHEDULED_OPEN.may_load(deps.storage, pair.clone())? {
+        if env.block.height < last_open + schedule.interval_blocks {
+            return Ok(false);
+        }
+    }
+
+    if active_auction.status != ActiveAuctionStatus::AuctionClosed {
+        return Ok(false);
+    }
+
+    let config = AUCTION_CONFIG.query(&deps.querier, pair_addr.clone())?;
+    if config.is_paused {
+        return Ok(false);
+    }
+
+    let auction_ids = AUCTION_IDS.query(&deps.querier, pair_addr.clone())?;
+    let next_funds = AUCTION_FUNDS_SUM
+        .query(&deps.querier, pair_addr.clone(), auction_ids.next)?
+        .unwrap_or_default();
+
+    // Below the start auction minimum the auction refunds the funds instead of starting
+    let min_start_auction = MIN_AUCTION_AMOUNT
+        .may_load(deps.storage, pair.0.clone())?
+        .unwrap_or_default()
+        .start_auction;
+
+    Ok(
+        !next_funds.is_zero()
+            && next_funds >= schedule.min_funds
+            && next_funds >= min_start_auction,
+    )
+}
+
 mod server {
     use cosmwasm_std::{ensure, to_json_binary, WasmMsg};
 
@@ -156,40 +663,91 @@ mod admin {
 
         match msg {
             AdminMsgs::NewAuction {
-                msg,
+                mut msg,
                 label,
                 min_amount,
+                template,
             } => {
-                msg.pair.verify()?;
+                let min_amount = match template {
+                    Some(template) => {
+                        let template = TEMPLATES
+                            .load(deps.storage, template.clone())
+                            .map_err(|_| ContractError::TemplateNotFound(template))?;
 
-                // Make sure we either set min_amount, or have it in storage
-                match min_amount {
-                    Some(min_amount) => {
-                        MIN_AUCTION_AMOUNT.save(deps.storage, msg.pair.0.clone(), &min_amount)?;
-                        Ok::<_, ContractError>(())
+                        msg.auction_strategy = template.auction_strategy;
+                        msg.chain_halt_config = template.chain_halt_config;
+                        msg.price_freshness_strategy = template.price_freshness_strategy;
+
+                        min_amount.or(template.min_amount)
                     }
-                    None => {
-                        MIN_AUCTION_AMOUNT
-                            .load(deps.storage, msg.pair.0.clone())
-                            .map_err(|_| {
-                                ContractError::MustSetMinAuctionAmount(msg.pair.0.clone())
-                            })?;
-                        Ok(())
+                    None => min_amount,
+                };
+
+                new_auction(deps, env, msg, label, min_amount)
+            }
+            AdminMsgs::SetTemplate { name, mut template } => {
+                // Templates are applied to live auctions, so we verify them same as a new auction
+                template.auction_strategy.verify()?;
+                template
+                    .price_freshness_strategy
+                    .multipliers
+                    .sort_by(|p1, p2| p2.0.cmp(&p1.0));
+
+                TEMPLATES.save(deps.storage, name.clone(), &template)?;
+
+                let event = ValenceEvent::AuctionManagerSetTemplate { name, template };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::RemoveTemplate { name } => {
+                if !TEMPLATES.has(deps.storage, name.clone()) {
+                    return Err(ContractError::TemplateNotFound(name));
+                }
+
+                TEMPLATES.remove(deps.storage, name.clone());
+
+                let event = ValenceEvent::AuctionManagerRemoveTemplate { name };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::ApplyTemplate { template, pairs } => {
+                let name = template;
+                let template = TEMPLATES
+                    .load(deps.storage, name.clone())
+                    .map_err(|_| ContractError::TemplateNotFound(name.clone()))?;
+
+                let mut msgs: Vec<WasmMsg> = Vec::with_capacity(pairs.len() * 3);
+                for pair in pairs.iter() {
+                    let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+                    let admin_msgs = [
+                        auction::msg::AdminMsgs::UpdateStrategy {
+                            strategy: template.auction_strategy.clone(),
+                        },
+                        auction::msg::AdminMsgs::UpdateChainHaltConfig(
+                            template.chain_halt_config.clone(),
+                        ),
+                        auction::msg::AdminMsgs::UpdatePriceFreshnessStrategy(
+                            template.price_freshness_strategy.clone(),
+                        ),
+                    ];
+
+                    for admin_msg in admin_msgs {
+                        msgs.push(WasmMsg::Execute {
+                            contract_addr: pair_addr.to_string(),
+                            msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                                admin_msg,
+                            )))?,
+                            funds: vec![],
+                        });
                     }
-                }?;
+                }
 
-                let init_msg = SubMsg::reply_on_success(
-                    WasmMsg::Instantiate {
-                        admin: Some(env.contract.address.to_string()),
-                        code_id: AUCTION_CODE_ID.load(deps.storage)?,
-                        msg: to_json_binary(&msg)?,
-                        funds: vec![],
-                        label,
-                    },
-                    INSTANTIATE_AUCTION_REPLY_ID,
-                );
+                let event = ValenceEvent::AuctionManagerApplyTemplate { name, pairs };
 
-                Ok(Response::default().add_submessage(init_msg))
+                Ok(Response::default()
+                    .add_event(event.into())
+                    .add_messages(msgs))
             }
             AdminMsgs::PauseAuction { pair } => {
                 let pair_addr = PAIRS.load(deps.storage, pair)?;
@@ -265,6 +823,78 @@ mod admin {
 
                 Ok(Response::default().add_message(msg))
             }
+            AdminMsgs::UpdateSettlementMode { pair, mode } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateSettlementMode(mode),
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
+            AdminMsgs::UpdateProtocolFee { pair, fee } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateProtocolFee(fee),
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
+            AdminMsgs::UpdateReclaimThreshold { pair, seconds } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateReclaimThreshold { seconds },
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
+            AdminMsgs::UpdateDustDestination { pair, destination } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateDustDestination(destination),
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
+            AdminMsgs::UpdateBidderGate { pair, gate } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateBidderGate(gate),
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
+            AdminMsgs::UpdateBidderAllowlist { pair, add, remove } => {
+                let pair_addr = PAIRS.load(deps.storage, pair)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::UpdateBidderAllowlist { add, remove },
+                    )))?,
+                    funds: vec![],
+                };
+
+                Ok(Response::default().add_message(msg))
+            }
             AdminMsgs::MigrateAuction { pair, code_id, msg } => {
                 let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
 
@@ -285,6 +915,80 @@ mod admin {
                     .add_event(event.into())
                     .add_message(migrate_msg))
             }
+            AdminMsgs::MigrateAllAuctions {
+                code_id,
+                msg,
+                start_after,
+                limit,
+            } => {
+                let limit = limit.unwrap_or(DEFAULT_MIGRATE_ALL_AUCTIONS_LIMIT) as usize;
+
+                if limit == 0 {
+                    return Err(ContractError::LimitIsZero);
+                }
+
+                // New auctions are created with the new code id
+                AUCTION_CODE_ID.save(deps.storage, &code_id)?;
+
+                let mut pairs = PAIRS
+                    .range(
+                        deps.storage,
+                        start_after.clone().map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .take(limit + 1)
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                let finished = pairs.len() <= limit;
+                pairs.truncate(limit);
+
+                // Continue the progress if we continue the same migration
+                let migrated = match MIGRATION_PROGRESS.may_load(deps.storage)? {
+                    Some(progress)
+                        if start_after.is_some()
+                            && progress.code_id == code_id
+                            && progress.last_migrated == start_after =>
+                    {
+                        progress.migrated
+                    }
+                    _ => 0,
+                };
+
+                let last_migrated = pairs.last().map(|(pair, _)| pair.clone()).or(start_after);
+
+                MIGRATION_PROGRESS.save(
+                    deps.storage,
+                    &MigrationProgress {
+                        code_id,
+                        last_migrated,
+                        migrated: migrated + pairs.len() as u64,
+                        finished,
+                    },
+                )?;
+
+                let migrate_msgs = pairs
+                    .iter()
+                    .map(|(_, pair_addr)| {
+                        Ok(WasmMsg::Migrate {
+                            contract_addr: pair_addr.to_string(),
+                            msg: to_json_binary(&msg)?,
+                            new_code_id: code_id,
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                let event: ValenceGenericEvent<auction::msg::MigrateMsg> =
+                    ValenceGenericEvent::AuctionManagerMigrateAllAuctions {
+                        pairs: pairs.into_iter().map(|(pair, _)| pair).collect(),
+                        code_id,
+                        msg,
+                    };
+
+                Ok(Response::default()
+                    .add_event(event.into())
+                    .add_messages(migrate_msgs))
+            }
             AdminMsgs::UpdateMinAmount { denom, min_amount } => {
                 MIN_AUCTION_AMOUNT.save(deps.storage, denom.clone(), &min_amount)?;
 
@@ -292,6 +996,129 @@ mod admin {
 
                 Ok(Response::default().add_event(event.into()))
             }
+            AdminMsgs::DeprecateAuction { pair } => {
+                let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+                DEPRECATED_PAIRS.save(deps.storage, pair.clone(), &Empty {})?;
+
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::Deprecate,
+                    )))?,
+                    funds: vec![],
+                };
+
+                let event = ValenceEvent::AuctionManagerDeprecateAuction { pair };
+
+                Ok(Response::default().add_event(event.into()).add_message(msg))
+            }
+            AdminMsgs::RefundDeprecatedLeftovers { pair, limit } => {
+                let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+                if !DEPRECATED_PAIRS.has(deps.storage, pair.clone()) {
+                    return Err(ContractError::AuctionNotDeprecated(pair));
+                }
+
+                let msg = WasmMsg::Execute {
+                    contract_addr: pair_addr.to_string(),
+                    msg: to_json_binary(&auction::msg::ExecuteMsg::Admin(Box::new(
+                        auction::msg::AdminMsgs::RefundLeftovers { limit },
+                    )))?,
+                    funds: vec![],
+                };
+
+                let event = ValenceEvent::AuctionManagerRefundDeprecatedLeftovers { pair };
+
+                Ok(Response::default().add_event(event.into()).add_message(msg))
+            }
+            AdminMsgs::RemoveAuction { pair } => {
+                let pair_addr = PAIRS.load(deps.storage, pair.clone())?;
+
+                if !DEPRECATED_PAIRS.has(deps.storage, pair.clone()) {
+                    return Err(ContractError::AuctionNotDeprecated(pair));
+                }
+
+                // The last auction must be closed, and no funds can be left in the auction,
+                // before we can remove the pair
+                let active_auction = ACTIVE_AUCTION.query(&deps.querier, pair_addr.clone())?;
+                if active_auction.status != ActiveAuctionStatus::AuctionClosed {
+                    return Err(ContractError::AuctionNotClosed(pair));
+                }
+
+                let has_unclaimed_settlements: bool = deps.querier.query_wasm_smart(
+                    pair_addr.clone(),
+                    &auction::msg::QueryMsg::HasUnclaimedSettlements,
+                )?;
+                if has_unclaimed_settlements {
+                    return Err(ContractError::AuctionHasUnclaimedSettlements(pair));
+                }
+
+                // Leftovers that are still being refunded or swept count as leftovers
+                let sweep: Option<DustSweep> = deps
+                    .querier
+                    .query_wasm_smart(pair_addr.clone(), &auction::msg::QueryMsg::GetDustSweep)?;
+                if sweep.is_some()
+                    || active_auction
+                        .leftovers
+                        .iter()
+                        .any(|leftover| !leftover.is_zero())
+                {
+                    return Err(ContractError::AuctionHasLeftovers(pair));
+                }
+
+                let auction_ids = AUCTION_IDS.query(&deps.querier, pair_addr.clone())?;
+                let next_funds = AUCTION_FUNDS_SUM
+                    .query(&deps.querier, pair_addr, auction_ids.next)?
+                    .unwrap_or_default();
+                if !next_funds.is_zero() {
+                    return Err(ContractError::AuctionHasFunds(pair));
+                }
+
+                PAIRS.remove(deps.storage, pair.clone());
+                DEPRECATED_PAIRS.remove(deps.storage, pair.clone());
+                AUCTION_SCHEDULES.remove(deps.storage, pair.clone());
+                LAST_SCHEDULED_OPEN.remove(deps.storage, pair.clone());
+
+                let event = ValenceEvent::AuctionManagerRemoveAuction { pair };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::SetAuctionSchedule { pair, schedule } => {
+                PAIRS.load(deps.storage, pair.clone())?;
+
+                if schedule.duration_blocks == 0 {
+                    return Err(ContractError::InvalidAuctionSchedule);
+                }
+
+                AUCTION_SCHEDULES.save(deps.storage, pair.clone(), &schedule)?;
+
+                let event = ValenceEvent::AuctionManagerSetAuctionSchedule { pair, schedule };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::RemoveAuctionSchedule { pair } => {
+                if !AUCTION_SCHEDULES.has(deps.storage, pair.clone()) {
+                    return Err(ContractError::AuctionScheduleNotFound(pair));
+                }
+
+                AUCTION_SCHEDULES.remove(deps.storage, pair.clone());
+                LAST_SCHEDULED_OPEN.remove(deps.storage, pair.clone());
+
+                let event = ValenceEvent::AuctionManagerRemoveAuctionSchedule { pair };
+
+                Ok(Response::default().add_event(event.into()))
+            }
+            AdminMsgs::UpdateKeeperTip { tip } => {
+                match &tip {
+                    Some(tip) => KEEPER_TIP.save(deps.storage, tip)?,
+                    None => KEEPER_TIP.remove(deps.storage),
+                }
+
+                let event = ValenceEvent::AuctionManagerUpdateKeeperTip { tip };
+
+                Ok(Response::default().add_event(event.into()))
+            }
             AdminMsgs::ChangeServerAddr { addr } => {
                 SERVER_ADDR.save(deps.storage, &deps.api.addr_validate(&addr)?)?;
 
@@ -311,10 +1138,45 @@ mod admin {
             }
         }
     }
+
+    fn new_auction(
+        deps: DepsMut,
+        env: Env,
+        msg: auction::msg::InstantiateMsg,
+        label: String,
+        min_amount: Option<MinAmount>,
+    ) -> Result<Response, ContractError> {
+        msg.pair.verify()?;
+
+        // Make sure we either set min_amount, or have it in storage
+        match min_amount {
+            Some(min_amount) => {
+                MIN_AUCTION_AMOUNT.save(deps.storage, msg.pair.0.clone(), &min_amount)?;
+            }
+            None => {
+                MIN_AUCTION_AMOUNT
+                    .load(deps.storage, msg.pair.0.clone())
+                    .map_err(|_| ContractError::MustSetMinAuctionAmount(msg.pair.0.clone()))?;
+            }
+        }
+
+        let init_msg = SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                admin: Some(env.contract.address.to_string()),
+                code_id: AUCTION_CODE_ID.load(deps.storage)?,
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
+                label,
+            },
+            INSTANTIATE_AUCTION_REPLY_ID,
+        );
+
+        Ok(Response::default().add_submessage(init_msg))
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: AuctionsManagerQueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: AuctionsManagerQueryMsg) -> StdResult<Binary> {
     match msg {
         AuctionsManagerQueryMsg::GetPairs { start_after, limit } => {
             let start_after = start_after.map(Bound::exclusive);
@@ -325,6 +1187,31 @@ pub fn query(deps: Deps, _env: Env, msg: AuctionsManagerQueryMsg) -> StdResult<B
 
             to_json_binary(&pairs)
         }
+        AuctionsManagerQueryMsg::GetAllActiveAuctions { start_after, limit } => {
+            let start_after = start_after.map(Bound::exclusive);
+            let limit = limit.unwrap_or(50) as usize;
+            let pairs = PAIRS
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            // Only return a cursor if there might be more pairs to scan
+            let last_scanned = if pairs.len() == limit {
+                pairs.last().map(|(pair, _)| pair.clone())
+            } else {
+                None
+            };
+
+            let auctions = pairs
+                .into_iter()
+                .filter_map(|(pair, addr)| get_active_auction(deps, &env, pair, addr).transpose())
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&ActiveAuctionsResponse {
+                auctions,
+                last_scanned,
+            })
+        }
         AuctionsManagerQueryMsg::GetPairAddr { pair } => {
             to_json_binary(&PAIRS.load(deps.storage, pair)?)
         }
@@ -349,12 +1236,69 @@ pub fn query(deps: Deps, _env: Env, msg: AuctionsManagerQueryMsg) -> StdResult<B
         }
         AuctionsManagerQueryMsg::GetAdmin => to_json_binary(&ADMIN.load(deps.storage)?),
         AuctionsManagerQueryMsg::GetServerAddr => to_json_binary(&SERVER_ADDR.load(deps.storage)?),
+        AuctionsManagerQueryMsg::GetAuctionSchedule { pair } => to_json_binary(
+            &AUCTION_SCHEDULES
+                .load(deps.storage, pair.clone())
+                .map_err(|_| ContractError::AuctionScheduleNotFound(pair))?,
+        ),
+        AuctionsManagerQueryMsg::GetMigrationProgress => {
+            to_json_binary(&MIGRATION_PROGRESS.may_load(deps.storage)?)
+        }
+        AuctionsManagerQueryMsg::GetKeeperTip => {
+            to_json_binary(&KEEPER_TIP.may_load(deps.storage)?)
+        }
+        AuctionsManagerQueryMsg::GetTemplate { name } => to_json_binary(
+            &TEMPLATES
+                .load(deps.storage, name.clone())
+                .map_err(|_| ContractError::TemplateNotFound(name))?,
+        ),
+        AuctionsManagerQueryMsg::GetTemplates { start_after, limit } => {
+            let start_after = start_after.map(Bound::exclusive);
+            let templates = TEMPLATES
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&templates)
+        }
+    }
+}
+
+/// Returns the auction data if the auction was opened and not closed yet
+fn get_active_auction(
+    deps: Deps,
+    env: &Env,
+    pair: Pair,
+    addr: Addr,
+) -> StdResult<Option<ActiveAuctionInfo>> {
+    let auction: auction::state::ActiveAuction = deps
+        .querier
+        .query_wasm_smart(addr.clone(), &auction::msg::QueryMsg::GetAuction)?;
+
+    if auction.status == ActiveAuctionStatus::AuctionClosed {
+        return Ok(None);
     }
+
+    // Price can only be calculated within the auction blocks
+    let height = env
+        .block
+        .height
+        .clamp(auction.start_block, auction.end_block);
+
+    Ok(Some(ActiveAuctionInfo {
+        pair,
+        addr,
+        price: calc_price(&auction, height),
+        status: auction.status,
+        available_amount: auction.available_amount,
+        end_block: auction.end_block,
+    }))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
+        AUCTION_STEP_REPLY_ID => handle_step_reply(deps, &env, msg.result),
         INSTANTIATE_AUCTION_REPLY_ID => {
             let auction_addr = deps
                 .api
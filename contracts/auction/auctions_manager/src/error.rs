@@ -1,4 +1,4 @@
-use auction_package::error::AuctionError;
+use auction_package::{error::AuctionError, Pair};
 use cosmwasm_std::StdError;
 use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
@@ -37,6 +37,42 @@ pub enum ContractError {
 
     #[error("Not the new admin")]
     AdminChangeExpired,
+
+    #[error("Auction schedule duration must be more than 0")]
+    InvalidAuctionSchedule,
+
+    #[error("Auction schedule for pair: {0} is missing")]
+    AuctionScheduleNotFound(Pair),
+
+    #[error("No auction is due to be opened")]
+    NoDueAuctions,
+
+    #[error("Limit must be more than 0")]
+    LimitIsZero,
+
+    #[error("CW20 token {0} is not pair.0 of the auction")]
+    InvalidCw20Token(String),
+
+    #[error("Auction of pair: {0} is deprecated")]
+    AuctionDeprecated(Pair),
+
+    #[error("Auction of pair: {0} must be deprecated first")]
+    AuctionNotDeprecated(Pair),
+
+    #[error("Auction of pair: {0} is not closed")]
+    AuctionNotClosed(Pair),
+
+    #[error("Auction of pair: {0} still has funds for the next auction")]
+    AuctionHasFunds(Pair),
+
+    #[error("Auction of pair: {0} still has leftovers from the last auction")]
+    AuctionHasLeftovers(Pair),
+
+    #[error("Auction of pair: {0} still has funds providers that didn't claim their share")]
+    AuctionHasUnclaimedSettlements(Pair),
+
+    #[error("Auction template: {0} is missing")]
+    TemplateNotFound(String),
 }
 
 impl From<ContractError> for StdError {
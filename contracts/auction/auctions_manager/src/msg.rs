@@ -1,8 +1,13 @@
 use auction::msg::NewAuctionParams;
 use auction_package::{
-    helpers::ChainHaltConfig, states::MinAmount, AuctionStrategy, Pair, PriceFreshnessStrategy,
+    helpers::{AuctionSchedule, AuctionTemplate, ChainHaltConfig},
+    states::MinAmount,
+    AuctionStrategy, BidderGate, DustDestination, Pair, PriceFreshnessStrategy, ProtocolFee,
+    SettlementMode,
 };
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Coin;
+use cw20::Cw20ReceiveMsg;
 use cw_utils::Expiration;
 
 #[cw_serde]
@@ -14,14 +19,49 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    AuctionFunds { pair: Pair },
-    WithdrawFunds { pair: Pair },
-    FinishAuction { pair: Pair, limit: u64 },
+    AuctionFunds {
+        pair: Pair,
+    },
+    /// Receive CW20 tokens to be auctioned, see `Cw20HookMsg`
+    Receive(Cw20ReceiveMsg),
+    WithdrawFunds {
+        pair: Pair,
+    },
+    /// Reclaim the share of the sender from the current auction of the pair,
+    /// once it was paused or the chain halted for longer than the reclaim threshold
+    ReclaimFunds {
+        pair: Pair,
+    },
+    FinishAuction {
+        pair: Pair,
+        limit: u64,
+    },
+    /// Go over `limit` schedules after `start_after`, and open the auctions that are due,
+    /// can be called by anyone
+    OpenDueAuctions {
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    },
+    /// Move the auctions of the pairs forward, can be called by anyone.
+    /// Finishes ended auctions, sweeps the dust and cleans closed auctions,
+    /// and opens auctions that are due.
+    /// If `pairs` is not set, goes over `limit` pairs of the manager at a time
+    Crank {
+        pairs: Option<Vec<Pair>>,
+        limit: Option<u32>,
+    },
     ApproveAdminChange {},
     Admin(Box<AdminMsgs>),
     Server(ServerMsgs),
 }
 
+/// Messages that can be sent with CW20 tokens
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Send the tokens to be auctioned on the next auction of the pair, the token must be pair.0
+    AuctionFunds { pair: Pair },
+}
+
 #[cw_serde]
 pub enum MigrateMsg {
     NoStateChange {},
@@ -42,10 +82,41 @@ pub enum AdminMsgs {
         msg: auction::msg::InstantiateMsg,
         label: String,
         min_amount: Option<MinAmount>,
+        /// Optional template to create the auction with, its config replaces the config in `msg`,
+        /// and its min amount is used if `min_amount` is not set
+        template: Option<String>,
+    },
+    /// Add or replace a named auction template
+    SetTemplate {
+        name: String,
+        template: AuctionTemplate,
+    },
+    RemoveTemplate {
+        name: String,
+    },
+    /// Update the config of the auctions of the pairs to the config of the template
+    ApplyTemplate {
+        template: String,
+        pairs: Vec<Pair>,
     },
     PauseAuction {
         pair: Pair,
     },
+    /// Stop accepting new funds to the auction of the pair,
+    /// funds that were already sent can still be withdrawn or auctioned
+    DeprecateAuction {
+        pair: Pair,
+    },
+    /// Refund all the leftovers of the last auction of a deprecated pair to its providers,
+    /// to `limit` providers at a time, the crank continues the refund
+    RefundDeprecatedLeftovers {
+        pair: Pair,
+        limit: Option<u64>,
+    },
+    /// Remove a deprecated pair once its auction is closed and it has no funds left
+    RemoveAuction {
+        pair: Pair,
+    },
     ResumeAuction {
         pair: Pair,
     },
@@ -71,14 +142,64 @@ pub enum AdminMsgs {
         pair: Pair,
         strategy: PriceFreshnessStrategy,
     },
+    UpdateSettlementMode {
+        pair: Pair,
+        mode: SettlementMode,
+    },
+    /// Set the fee taken from the resolved pair.1 of the auction, None to stop taking it
+    UpdateProtocolFee {
+        pair: Pair,
+        fee: Option<ProtocolFee>,
+    },
+    /// Set the seconds the auction must be paused or the chain halted
+    /// before funds can be reclaimed from the current auction
+    UpdateReclaimThreshold {
+        pair: Pair,
+        seconds: u64,
+    },
+    /// Set where dust leftovers of the auction are swept to, None to roll them into the next auction
+    UpdateDustDestination {
+        pair: Pair,
+        destination: Option<DustDestination>,
+    },
+    /// Set who can bid on the auction, None to allow anyone to bid
+    UpdateBidderGate {
+        pair: Pair,
+        gate: Option<BidderGate>,
+    },
+    /// Add and remove addresses from the bidder allowlist of the auction
+    UpdateBidderAllowlist {
+        pair: Pair,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
     MigrateAuction {
         pair: Pair,
         code_id: u64,
         msg: auction::msg::MigrateMsg,
     },
+    /// Migrate up to `limit` auctions after `start_after` to the code id,
+    /// and set it as the code id of new auctions
+    MigrateAllAuctions {
+        code_id: u64,
+        msg: auction::msg::MigrateMsg,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    },
     ChangeServerAddr {
         addr: String,
     },
+    SetAuctionSchedule {
+        pair: Pair,
+        schedule: AuctionSchedule,
+    },
+    RemoveAuctionSchedule {
+        pair: Pair,
+    },
+    /// Set the tip paid for every auction opened by `OpenDueAuctions`, None to stop paying
+    UpdateKeeperTip {
+        tip: Option<Coin>,
+    },
     StartAdminChange {
         addr: String,
         expiration: Expiration,
@@ -1,5 +1,55 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use std::{collections::VecDeque, fmt};
+
+use auction_package::{
+    helpers::{AuctionSchedule, AuctionTemplate, MigrationProgress},
+    Pair,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Item, Map};
 
 pub const SERVER_ADDR: Item<Addr> = Item::new("server_addr");
 pub const AUCTION_CODE_ID: Item<u64> = Item::new("auction_code_id");
+/// When to open the auction of the pair with `OpenDueAuctions`
+pub const AUCTION_SCHEDULES: Map<Pair, AuctionSchedule> = Map::new("auction_schedules");
+/// The block the auction of the pair was last opened by `OpenDueAuctions`
+pub const LAST_SCHEDULED_OPEN: Map<Pair, u64> = Map::new("last_scheduled_open");
+/// The last pair `Crank` went over, the next crank continues from it
+pub const CRANK_CURSOR: Item<Pair> = Item::new("crank_cursor");
+/// Steps sent to the auctions by `OpenDueAuctions` or `Crank` that didn't reply yet
+pub const PENDING_STEPS: Item<PendingSteps> = Item::new("pending_steps");
+/// Progress of the last `MigrateAllAuctions`
+pub const MIGRATION_PROGRESS: Item<MigrationProgress> = Item::new("migration_progress");
+/// Paid to the sender of `OpenDueAuctions` for every auction opened
+pub const KEEPER_TIP: Item<Coin> = Item::new("keeper_tip");
+/// Named auction templates, used to create and update auctions
+pub const TEMPLATES: Map<String, AuctionTemplate> = Map::new("templates");
+
+/// A message the manager sends to an auction as a sub message, so a failing auction
+/// doesn't fail the other auctions of the batch
+#[cw_serde]
+pub enum AuctionStep {
+    Finish,
+    SweepDust,
+    Clean,
+    Open,
+}
+
+impl fmt::Display for AuctionStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuctionStep::Finish => write!(f, "finish"),
+            AuctionStep::SweepDust => write!(f, "sweep-dust"),
+            AuctionStep::Clean => write!(f, "clean"),
+            AuctionStep::Open => write!(f, "open"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PendingSteps {
+    /// The sender of the batch, paid the keeper tip for every auction opened
+    pub keeper: Addr,
+    /// The steps in the order they were sent, replies come back in the same order
+    pub steps: VecDeque<(Pair, AuctionStep)>,
+}
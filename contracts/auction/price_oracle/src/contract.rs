@@ -1,27 +1,39 @@
 use std::collections::VecDeque;
 
 use auction_package::helpers::{
-    approve_admin_change, cancel_admin_change, start_admin_change, verify_admin,
+    approve_admin_change, cancel_admin_change, start_admin_change, verify_admin, GetPriceResponse,
 };
+use auction_package::msgs::AuctionsManagerQueryMsg;
 use auction_package::states::{ADMIN, PAIRS, PRICES, TWAP_PRICES};
 use auction_package::{Pair, Price};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use valence_package::event_indexing::{ValenceEvent, ValenceGenericEvent};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, PriceStep, ASTRO_PRICE_PATHS, CONFIG, LOCAL_PRICES};
+use crate::msg::{
+    ExecuteMsg, HealthResponse, InstantiateMsg, MigrateMsg, PriceDetailsResponse, QueryMsg,
+    StalePricesResponse,
+};
+use crate::sources::{get_price_from_astroport, save_astro_cumulative_prices, verify_astro_path};
+use crate::state::{
+    Config, PendingPrice, PriceHistoryEntry, PriceStep, Role, RoleScope, TwapConfig,
+    ASTRO_PRICE_PATHS, CONFIG, LOCAL_PRICES, PENDING_PRICES, PRICE_HISTORY, PRICE_SOURCES,
+    TWAP_CONFIGS, UPDATE_PRICES_CURSOR,
+};
 
 const CONTRACT_NAME: &str = "crates.io:oracle";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-
-const TWAP_PRICE_MAX_LEN: usize = 10;
+/// The default amount of pairs we update in a single `UpdatePrices` batch
+const DEFAULT_UPDATE_PRICES_LIMIT: u32 = 10;
+/// The default amount of pairs we check in a single `GetHealth` query
+const DEFAULT_HEALTH_LIMIT: u32 = 10;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -41,6 +53,8 @@ pub fn instantiate(
             auction_manager_addr: deps.api.addr_validate(&msg.auctions_manager_addr)?,
             seconds_allow_manual_change: msg.seconds_allow_manual_change,
             seconds_auction_prices_fresh: msg.seconds_auction_prices_fresh,
+            max_price_deviation_bps: None,
+            anchor_denom: None,
         },
     )?;
 
@@ -56,53 +70,71 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdatePrice { pair } => {
-            pair.verify()?;
-
             let config = CONFIG.load(deps.storage)?;
+            let event = update_pair_price(deps, &env, &info.sender, &config, pair)?;
 
-            // We get the prices from the auction
-            let auction_addr = PAIRS
-                .query(
-                    &deps.querier,
-                    config.auction_manager_addr.clone(),
-                    pair.clone(),
-                )?
-                .ok_or(ContractError::PairAuctionNotFound)?;
-            let auction_twap_prices = TWAP_PRICES.query(&deps.querier, auction_addr)?;
-
-            let source;
-
-            // We get last price either form auction or astroport
-            let last_price = if can_update_price_from_auction(&config, &env, &auction_twap_prices) {
-                source = "auction";
-                auction_twap_prices[0].clone()
-            } else {
-                let steps = ASTRO_PRICE_PATHS
-                    .load(deps.storage, pair.clone())
-                    .map_err(|_| ContractError::NoAstroPath(pair.clone()))?;
-                source = "astroport";
-                get_price_from_astroport(deps.as_ref(), &env, steps)?
-            };
-
-            let local_prices = update_local_price(deps.branch(), pair.clone(), last_price.clone())?;
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::UpdatePrices { pairs, limit } => {
+            let config = CONFIG.load(deps.storage)?;
+            let limit = limit.unwrap_or(DEFAULT_UPDATE_PRICES_LIMIT) as usize;
 
-            // Calculate the average price
-            let avg_price = get_avg_price(local_prices);
+            if limit == 0 {
+                return Err(ContractError::LimitIsZero);
+            }
 
-            // Save price
-            PRICES.save(deps.storage, pair.clone(), &avg_price)?;
+            let pairs = match pairs {
+                Some(pairs) => pairs,
+                None => {
+                    // Continue from the last pair the previous batch updated
+                    let start_after = UPDATE_PRICES_CURSOR.may_load(deps.storage)?;
+                    let mut pairs: Vec<Pair> = deps
+                        .querier
+                        .query_wasm_smart::<Vec<(Pair, Addr)>>(
+                            config.auction_manager_addr.clone(),
+                            &AuctionsManagerQueryMsg::GetPairs {
+                                start_after,
+                                limit: Some((limit as u32).saturating_add(1)),
+                            },
+                        )?
+                        .into_iter()
+                        .map(|(pair, _)| pair)
+                        .collect();
+
+                    // If we got more than the limit, the next batch continues from the last pair
+                    if pairs.len() > limit {
+                        pairs.truncate(limit);
+                        UPDATE_PRICES_CURSOR.save(deps.storage, &pairs[limit - 1])?;
+                    } else {
+                        UPDATE_PRICES_CURSOR.remove(deps.storage);
+                    }
 
-            let event = ValenceEvent::OracleUpdatePrice {
-                pair: pair.clone(),
-                price: avg_price.price,
-                source: source.to_string(),
+                    pairs
+                }
             };
 
-            Ok(Response::default().add_event(event.into()))
+            let mut response = Response::default();
+
+            for pair in pairs {
+                // A failed pair doesn't fail the batch, we emit the error instead
+                let res =
+                    update_pair_price(deps.branch(), &env, &info.sender, &config, pair.clone());
+                let event = match res {
+                    Ok(event) => event,
+                    Err(err) => ValenceEvent::OracleUpdatePriceFailed {
+                        pair,
+                        error: err.to_string(),
+                    },
+                };
+
+                response = response.add_event(event.into());
+            }
+
+            Ok(response)
         }
         ExecuteMsg::ManualPriceUpdate { pair, price } => {
             let config = CONFIG.load(deps.storage)?;
-            verify_admin(deps.as_ref(), &info)?;
+            verify_role(deps.as_ref(), &info.sender, Role::ManualPriceSigner, &pair)?;
 
             pair.verify()?;
 
@@ -133,18 +165,21 @@ pub fn execute(
                 price,
                 time: env.block.time,
             };
-            let local_prices = update_local_price(deps.branch(), pair.clone(), price.clone())?;
-
-            // Calculate the average price
-            let avg_price = get_avg_price(local_prices);
+            let source = "manual".to_string();
 
-            // Save price
-            PRICES.save(deps.storage, pair.clone(), &avg_price)?;
+            let published = handle_new_price(deps.branch(), &env, &config, &pair, &price, &source)?;
 
-            let event = ValenceEvent::OracleUpdatePrice {
-                pair,
-                price: price.price,
-                source: "manual".to_string(),
+            let event = match published {
+                Some(_) => ValenceEvent::OracleUpdatePrice {
+                    pair,
+                    price: price.price,
+                    source,
+                },
+                None => ValenceEvent::OracleHoldPendingPrice {
+                    pair,
+                    price: price.price,
+                    source,
+                },
             };
 
             Ok(Response::default().add_event(event.into()))
@@ -158,13 +193,7 @@ pub fn execute(
                 return Err(ContractError::PricePathAlreadyExists);
             }
 
-            if path.is_empty() {
-                return Err(ContractError::PricePathIsEmpty);
-            }
-
-            if path[0].denom1 != pair.0 || path[path.len() - 1].denom2 != pair.1 {
-                return Err(ContractError::PricePathIsWrong);
-            }
+            verify_astro_path(&pair, &path)?;
 
             ASTRO_PRICE_PATHS.save(deps.storage, pair.clone(), &path)?;
 
@@ -181,17 +210,82 @@ pub fn execute(
                 return Err(ContractError::PricePathNotFound);
             }
 
-            if path.is_empty() {
-                return Err(ContractError::PricePathIsEmpty);
+            verify_astro_path(&pair, &path)?;
+
+            ASTRO_PRICE_PATHS.save(deps.storage, pair.clone(), &path)?;
+
+            let event = ValenceGenericEvent::OracleUpdatePath { pair, path };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::SetPriceSources { pair, sources } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            pair.verify()?;
+            sources.verify(deps.as_ref(), &env, &pair)?;
+
+            PRICE_SOURCES.save(deps.storage, pair.clone(), &sources)?;
+
+            let event = ValenceGenericEvent::OracleSetPriceSources { pair, sources };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::RemovePriceSources { pair } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            if !PRICE_SOURCES.has(deps.storage, pair.clone()) {
+                return Err(ContractError::PriceSourceNotFound(pair));
             }
 
-            if path[0].denom1 != pair.0 || path[path.len() - 1].denom2 != pair.1 {
-                return Err(ContractError::PricePathIsWrong);
+            PRICE_SOURCES.remove(deps.storage, pair.clone());
+
+            let event = ValenceEvent::OracleRemovePriceSources { pair };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::UpdateTwapConfig { pair, config } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            pair.verify()?;
+
+            if config.window_seconds == 0 || config.max_prices == 0 {
+                return Err(ContractError::InvalidTwapConfig);
             }
 
-            ASTRO_PRICE_PATHS.save(deps.storage, pair.clone(), &path)?;
+            TWAP_CONFIGS.save(deps.storage, pair.clone(), &config)?;
 
-            let event = ValenceGenericEvent::OracleUpdatePath { pair, path };
+            let event = ValenceGenericEvent::OracleUpdateTwapConfig { pair, config };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::ApprovePendingPrice { pair } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            let pending = PENDING_PRICES
+                .may_load(deps.storage, pair.clone())?
+                .ok_or(ContractError::PendingPriceNotFound(pair.clone()))?;
+            PENDING_PRICES.remove(deps.storage, pair.clone());
+
+            let avg_price =
+                publish_price(deps.branch(), &env, &pair, &pending.price, &pending.source)?;
+
+            let event = ValenceEvent::OracleApprovePendingPrice {
+                pair,
+                price: avg_price.price,
+            };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::RejectPendingPrice { pair } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            if !PENDING_PRICES.has(deps.storage, pair.clone()) {
+                return Err(ContractError::PendingPriceNotFound(pair));
+            }
+
+            PENDING_PRICES.remove(deps.storage, pair.clone());
+
+            let event = ValenceEvent::OracleRejectPendingPrice { pair };
 
             Ok(Response::default().add_event(event.into()))
         }
@@ -199,6 +293,8 @@ pub fn execute(
             auction_manager_addr,
             seconds_allow_manual_change,
             seconds_auction_prices_fresh,
+            max_price_deviation_bps,
+            anchor_denom,
         } => {
             verify_admin(deps.as_ref(), &info)?;
 
@@ -216,12 +312,66 @@ pub fn execute(
                 config.seconds_auction_prices_fresh = seconds_auction_prices_fresh;
             }
 
+            if let Some(max_price_deviation_bps) = max_price_deviation_bps {
+                config.max_price_deviation_bps = match max_price_deviation_bps {
+                    0 => None,
+                    bps => Some(bps),
+                };
+            }
+
+            if let Some(anchor_denom) = anchor_denom {
+                config.anchor_denom = Some(anchor_denom).filter(|denom| !denom.is_empty());
+            }
+
             CONFIG.save(deps.storage, &config)?;
 
             let event = ValenceGenericEvent::OracleUpdateConfig { config };
 
             Ok(Response::default().add_event(event.into()))
         }
+        ExecuteMsg::AddRole { role, addr, pairs } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            let addr = deps.api.addr_validate(&addr)?;
+
+            if let Some(pairs) = &pairs {
+                pairs.iter().try_for_each(|pair| pair.verify())?;
+            }
+
+            role.members().save(
+                deps.storage,
+                addr.clone(),
+                &RoleScope {
+                    pairs: pairs.clone(),
+                },
+            )?;
+
+            let event = ValenceEvent::OracleAddRole {
+                role: role.to_string(),
+                addr: addr.to_string(),
+                pairs,
+            };
+
+            Ok(Response::default().add_event(event.into()))
+        }
+        ExecuteMsg::RemoveRole { role, addr } => {
+            verify_admin(deps.as_ref(), &info)?;
+
+            let addr = deps.api.addr_validate(&addr)?;
+
+            if !role.members().has(deps.storage, addr.clone()) {
+                return Err(ContractError::RoleNotFound(role));
+            }
+
+            role.members().remove(deps.storage, addr.clone());
+
+            let event = ValenceEvent::OracleRemoveRole {
+                role: role.to_string(),
+                addr: addr.to_string(),
+            };
+
+            Ok(Response::default().add_event(event.into()))
+        }
         ExecuteMsg::StartAdminChange { addr, expiration } => {
             let event = ValenceEvent::OracleStartAdminChange {
                 admin: addr.clone(),
@@ -259,10 +409,202 @@ fn can_update_price_from_auction(
     true
 }
 
-fn update_local_price(
+/// Update the price of the pair from the auction, the price sources of the pair or astroport.
+///
+/// All the queries are done before we save anything,
+/// so a failed update doesn't leave a partial state behind in a batch.
+fn update_pair_price(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    config: &Config,
+    pair: Pair,
+) -> Result<ValenceEvent, ContractError> {
+    pair.verify()?;
+    verify_role(deps.as_ref(), sender, Role::Updater, &pair)?;
+
+    // We get the prices from the auction
+    let auction_addr = PAIRS
+        .query(
+            &deps.querier,
+            config.auction_manager_addr.clone(),
+            pair.clone(),
+        )?
+        .ok_or(ContractError::PairAuctionNotFound)?;
+    let auction_twap_prices = TWAP_PRICES.query(&deps.querier, auction_addr)?;
+
+    let price_sources = PRICE_SOURCES.may_load(deps.storage, pair.clone())?;
+    let source;
+    let astro_steps: Vec<PriceStep>;
+
+    // We get last price either form auction, the price sources of the pair or astroport
+    let last_price = if can_update_price_from_auction(config, env, &auction_twap_prices) {
+        source = "auction".to_string();
+        astro_steps = vec![];
+        auction_twap_prices[0].clone()
+    } else if let Some(price_sources) = price_sources {
+        source = price_sources.name().to_string();
+        astro_steps = price_sources.astro_steps().cloned().collect();
+        price_sources.query_price(deps.as_ref(), env, &pair)?
+    } else {
+        let steps = ASTRO_PRICE_PATHS
+            .load(deps.storage, pair.clone())
+            .map_err(|_| ContractError::NoAstroPath(pair.clone()))?;
+        source = "astroport".to_string();
+        let price = get_price_from_astroport(deps.as_ref(), env, &steps)?;
+        astro_steps = steps;
+        price
+    };
+
+    let published = handle_new_price(deps.branch(), env, config, &pair, &last_price, &source)?;
+    save_astro_cumulative_prices(deps, env, astro_steps.iter())?;
+
+    let event = match published {
+        Some(avg_price) => ValenceEvent::OracleUpdatePrice {
+            pair,
+            price: avg_price.price,
+            source,
+        },
+        None => ValenceEvent::OracleHoldPendingPrice {
+            pair,
+            price: last_price.price,
+            source,
+        },
+    };
+
+    Ok(event)
+}
+
+/// Verify the sender has the role for the pair, the admin has all roles.
+fn verify_role(deps: Deps, sender: &Addr, role: Role, pair: &Pair) -> Result<(), ContractError> {
+    if ADMIN.load(deps.storage)? == *sender {
+        return Ok(());
+    }
+
+    let members = role.members();
+
+    // Price updates are permissionless until we have an updater
+    if role == Role::Updater
+        && members
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    {
+        return Ok(());
+    }
+
+    match members.may_load(deps.storage, sender.clone())? {
+        Some(RoleScope { pairs: None }) => Ok(()),
+        Some(RoleScope { pairs: Some(pairs) }) if pairs.contains(pair) => Ok(()),
+        _ => Err(ContractError::MissingRole(role, pair.clone())),
+    }
+}
+
+/// Publish the new price of the pair, unless it deviates too much from the current price.
+///
+/// A deviating price is held as pending, and is only published if the next update
+/// confirms it in a later block, or the admin approves it.
+/// Returns the published average price, or None if the price is pending.
+fn handle_new_price(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    pair: &Pair,
+    price: &Price,
+    source: &str,
+) -> Result<Option<Price>, ContractError> {
+    if let Some(max_price_deviation_bps) = config.max_price_deviation_bps {
+        let max_deviation = Decimal::bps(max_price_deviation_bps);
+
+        let is_confirmed = match PENDING_PRICES.may_load(deps.storage, pair.clone())? {
+            Some(pending) => {
+                pending.price.time < price.time
+                    && is_within_deviation(pending.price.price, price.price, max_deviation)
+            }
+            None => false,
+        };
+        let is_deviating = match PRICES.may_load(deps.storage, pair.clone())? {
+            Some(current) => !is_within_deviation(current.price, price.price, max_deviation),
+            None => false,
+        };
+
+        if is_deviating && !is_confirmed {
+            PENDING_PRICES.save(
+                deps.storage,
+                pair.clone(),
+                &PendingPrice {
+                    price: price.clone(),
+                    source: source.to_string(),
+                },
+            )?;
+            return Ok(None);
+        }
+    }
+
+    PENDING_PRICES.remove(deps.storage, pair.clone());
+    Ok(Some(publish_price(deps, env, pair, price, source)?))
+}
+
+fn is_within_deviation(current: Decimal, new: Decimal, max_deviation: Decimal) -> bool {
+    let diff = if new > current {
+        new - current
+    } else {
+        current - new
+    };
+    diff <= current * max_deviation
+}
+
+/// Add the price to the local prices and the history, and save the new average price of the pair
+fn publish_price(
     deps: DepsMut,
+    env: &Env,
+    pair: &Pair,
+    price: &Price,
+    source: &str,
+) -> Result<Price, ContractError> {
+    let twap_config = get_twap_config(deps.as_ref(), pair)?;
+    let local_prices = add_local_price(deps.as_ref(), pair.clone(), price.clone(), &twap_config)?;
+
+    // Calculate the time weighted average price
+    let avg_price = get_twap_price(env, &local_prices, &twap_config)?;
+
+    // Save prices
+    LOCAL_PRICES.save(deps.storage, pair.clone(), &local_prices)?;
+    PRICES.save(deps.storage, pair.clone(), &avg_price)?;
+
+    let next_id = match PRICE_HISTORY
+        .prefix(pair.clone())
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+    {
+        Some(last_id) => last_id? + 1,
+        None => 0,
+    };
+    PRICE_HISTORY.save(
+        deps.storage,
+        (pair.clone(), next_id),
+        &PriceHistoryEntry {
+            price: price.price,
+            average_price: avg_price.price,
+            time: price.time,
+            source: source.to_string(),
+        },
+    )?;
+
+    Ok(avg_price)
+}
+
+fn get_twap_config(deps: Deps, pair: &Pair) -> StdResult<TwapConfig> {
+    Ok(TWAP_CONFIGS
+        .may_load(deps.storage, pair.clone())?
+        .unwrap_or_default())
+}
+
+fn add_local_price(
+    deps: Deps,
     pair: Pair,
     price: Price,
+    twap_config: &TwapConfig,
 ) -> Result<VecDeque<Price>, cosmwasm_std::StdError> {
     // Update the oracle local prices and add last price
     let mut local_prices = match LOCAL_PRICES.load(deps.storage, pair.clone()) {
@@ -270,85 +612,191 @@ fn update_local_price(
         Err(_) => VecDeque::new(),
     };
 
-    // if we have the max amount of prices already, remove the last one first
-    if local_prices.len() >= TWAP_PRICE_MAX_LEN {
+    // if we have the max amount of prices already, remove the last ones first
+    while local_prices.len() >= twap_config.max_prices as usize {
         local_prices.pop_back();
     }
 
     // Push the last price into the vector
-    local_prices.push_front(price.clone());
+    local_prices.push_front(price);
 
-    // Save the new list of prices
-    LOCAL_PRICES.save(deps.storage, pair.clone(), &local_prices)?;
     Ok(local_prices)
 }
 
-fn get_avg_price(vec: VecDeque<Price>) -> Price {
-    let (total_count, prices_sum) = vec.iter().fold(
+/// Calculate the time weighted average of the local prices in the window of the pair.
+///
+/// Every price is weighted by the time it was the latest price, until the next price,
+/// clamped to the window, so a burst of updates in a short time doesn't dominate the average.
+///
+/// The newest price was just published, so it is weighted by the time the previous price
+/// was the latest price, as the time we expect it to be the latest price.
+fn get_twap_price(
+    env: &Env,
+    prices: &VecDeque<Price>,
+    twap_config: &TwapConfig,
+) -> Result<Price, ContractError> {
+    let now = env.block.time.seconds();
+    let window_start = now.saturating_sub(twap_config.window_seconds);
+
+    // Prices are ordered from the newest to the oldest
+    let newest_time = prices[0].time.seconds();
+    let newest_duration = prices.get(1).map_or(0, |previous| {
+        newest_time
+            .saturating_sub(previous.time.seconds())
+            .min(twap_config.window_seconds)
+    });
+    let newest_end = now.max(newest_time + newest_duration);
+    let ends = std::iter::once(newest_end).chain(prices.iter().map(|price| price.time.seconds()));
+
+    let (total_weight, weighted_sum) = prices.iter().zip(ends).try_fold(
         (Decimal::zero(), Decimal::zero()),
-        |(total_count, prices_sum), price| (total_count + Decimal::one(), prices_sum + price.price),
-    );
+        |(total_weight, weighted_sum), (price, end)| {
+            let start = price.time.seconds().max(window_start);
+            let weight = Decimal::from_atomics(end.saturating_sub(start), 0)?;
+
+            Ok::<_, ContractError>((
+                total_weight.checked_add(weight)?,
+                weighted_sum.checked_add(price.price.checked_mul(weight)?)?,
+            ))
+        },
+    )?;
 
-    Price {
-        price: prices_sum / total_count,
-        time: vec[0].time,
-    }
+    // No time passed since the prices in the window, use the last price
+    let price = if total_weight.is_zero() {
+        prices[0].price
+    } else {
+        weighted_sum.checked_div(total_weight)?
+    };
+
+    Ok(Price {
+        price,
+        time: prices[0].time,
+    })
 }
 
-fn get_price_from_astroport(
+/// Go over a page of the pairs of the auctions manager and report the ones that need attention
+fn get_health(
     deps: Deps,
     env: &Env,
-    steps: Vec<PriceStep>,
-) -> Result<Price, ContractError> {
-    let final_denom_amount = steps.iter().fold(
-        Decimal::from_atomics(1000000_u128, 0).map_err(ContractError::DecimalRangeExceeded),
-        |amount, step| {
-            // Build the asset
-            let offer_asset = astroport::asset::Asset {
-                info: astroport::asset::AssetInfo::NativeToken {
-                    denom: step.denom1.clone(),
-                },
-                amount: amount?.to_uint_floor(),
-            };
+    start_after: Option<Pair>,
+    limit: Option<u32>,
+) -> Result<HealthResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_HEALTH_LIMIT);
+    let mut health = HealthResponse::default();
+
+    let pairs: Vec<(Pair, Addr)> = deps.querier.query_wasm_smart(
+        config.auction_manager_addr.clone(),
+        &AuctionsManagerQueryMsg::GetPairs {
+            start_after,
+            limit: Some(limit),
+        },
+    )?;
 
-            let res = astroport::querier::simulate(
-                &deps.querier,
-                step.pool_address.clone(),
-                &offer_asset,
-            )?;
+    // Only return a cursor if there might be more pairs to check
+    if pairs.len() == limit as usize {
+        health.last_checked = pairs.last().map(|(pair, _)| pair.clone());
+    }
 
-            let price = Decimal::from_atomics(
-                res.return_amount
-                    .checked_add(res.commission_amount)?
-                    .checked_add(res.spread_amount)?,
-                0,
-            )?;
-            // deps.api.debug(format!("res: {:?}", res).as_str());
-            // deps.api.debug(format!("Price step: {:?}", price).as_str());
+    for (pair, auction_addr) in pairs {
+        match PRICES.may_load(deps.storage, pair.clone())? {
+            Some(price) => {
+                if price.time.seconds() + config.seconds_allow_manual_change
+                    <= env.block.time.seconds()
+                {
+                    health.past_manual_change.push(pair.clone());
+                }
+            }
+            None => health.no_price.push(pair.clone()),
+        }
 
-            Ok(price)
-        },
-    )?;
+        if !ASTRO_PRICE_PATHS.has(deps.storage, pair.clone())
+            && !PRICE_SOURCES.has(deps.storage, pair.clone())
+        {
+            health.no_astro_path.push(pair.clone());
+        }
 
-    let _price = final_denom_amount.checked_div(Decimal::from_atomics(1000000_u128, 0)?)?;
-    deps.api.debug(format!("Price: {:?}", _price).as_str());
+        let auction_twap_prices = TWAP_PRICES.query(&deps.querier, auction_addr)?;
+        if !can_update_price_from_auction(&config, env, &auction_twap_prices) {
+            health.auction_twap_too_short.push(pair);
+        }
+    }
 
-    let price = Price {
-        price: final_denom_amount.checked_div(Decimal::from_atomics(1000000_u128, 0)?)?,
-        time: env.block.time,
-    };
+    Ok(health)
+}
+
+/// Get the price of the pair, if we don't have it, derive it from the inverse pair,
+/// or from the prices of both denoms against the anchor denom (A/B = A/anchor * anchor/B).
+/// A derived price is only as fresh as the oldest price it was derived from.
+fn get_price(deps: Deps, pair: Pair) -> Result<PriceDetailsResponse, ContractError> {
+    if let Some(price) = PRICES.may_load(deps.storage, pair.clone())? {
+        return Ok(PriceDetailsResponse {
+            price: price.price,
+            time: price.time,
+            derived: false,
+        });
+    }
 
-    Ok(price)
+    if let Some(price) = get_inverse_price(deps, &pair)? {
+        return Ok(PriceDetailsResponse {
+            price: price.price,
+            time: price.time,
+            derived: true,
+        });
+    }
+
+    if let Some(anchor_denom) = CONFIG.load(deps.storage)?.anchor_denom {
+        if anchor_denom != pair.0 && anchor_denom != pair.1 {
+            let first_pair = Pair::from((pair.0.clone(), anchor_denom.clone()));
+            let second_pair = Pair::from((anchor_denom, pair.1.clone()));
+
+            if let (Some(first), Some(second)) = (
+                get_stored_or_inverse_price(deps, &first_pair)?,
+                get_stored_or_inverse_price(deps, &second_pair)?,
+            ) {
+                return Ok(PriceDetailsResponse {
+                    price: first.price.checked_mul(second.price)?,
+                    time: first.time.min(second.time),
+                    derived: true,
+                });
+            }
+        }
+    }
+
+    Err(ContractError::PriceNotFound(pair))
+}
+
+/// Get the stored price of the pair, or the inverse of the stored price of the inverse pair
+fn get_stored_or_inverse_price(deps: Deps, pair: &Pair) -> Result<Option<Price>, ContractError> {
+    match PRICES.may_load(deps.storage, pair.clone())? {
+        Some(price) => Ok(Some(price)),
+        None => get_inverse_price(deps, pair),
+    }
+}
+
+fn get_inverse_price(deps: Deps, pair: &Pair) -> Result<Option<Price>, ContractError> {
+    let inverse_pair = Pair::from((pair.1.clone(), pair.0.clone()));
+
+    match PRICES.may_load(deps.storage, inverse_pair)? {
+        Some(price) => Ok(Some(Price {
+            price: Decimal::one().checked_div(price.price)?,
+            time: price.time,
+        })),
+        None => Ok(None),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::GetPrice { pair } => {
-            let price = PRICES.load(deps.storage, pair)?;
-
-            Ok(to_json_binary(&price)?)
+            let price = get_price(deps, pair)?;
+            Ok(to_json_binary(&GetPriceResponse {
+                price: price.price,
+                time: price.time,
+            })?)
         }
+        QueryMsg::GetPriceDetails { pair } => Ok(to_json_binary(&get_price(deps, pair)?)?),
         QueryMsg::GetLocalPrice { pair } => {
             let price: Vec<Price> = LOCAL_PRICES.load(deps.storage, pair)?.into();
 
@@ -363,6 +811,99 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
 
             Ok(to_json_binary(&prices)?)
         }
+        QueryMsg::GetPriceHistory {
+            pair,
+            start_after,
+            limit,
+        } => {
+            let history = PRICE_HISTORY
+                .prefix(pair)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&history)?)
+        }
+        QueryMsg::GetStalePrices {
+            max_age_seconds,
+            start_after,
+            limit,
+        } => {
+            let now = env.block.time.seconds();
+            let limit = limit.unwrap_or(DEFAULT_HEALTH_LIMIT);
+            let start_after = start_after.map(Bound::<Pair>::exclusive);
+
+            // The limit is the amount of prices we check, not the amount of stale prices we return
+            let prices = PRICES
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            // Only return a cursor if there might be more prices to check
+            let last_checked = if prices.len() == limit as usize {
+                prices.last().map(|(pair, _)| pair.clone())
+            } else {
+                None
+            };
+
+            Ok(to_json_binary(&StalePricesResponse {
+                prices: prices
+                    .into_iter()
+                    .filter(|(_, price)| price.time.seconds().saturating_add(max_age_seconds) < now)
+                    .collect(),
+                last_checked,
+            })?)
+        }
+        QueryMsg::GetHealth { start_after, limit } => Ok(to_json_binary(&get_health(
+            deps,
+            &env,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetPriceSources { pair } => {
+            let sources = PRICE_SOURCES
+                .load(deps.storage, pair.clone())
+                .map_err(|_| ContractError::PriceSourceNotFound(pair))?;
+
+            Ok(to_json_binary(&sources)?)
+        }
+        QueryMsg::GetPendingPrice { pair } => Ok(to_json_binary(
+            &PENDING_PRICES.may_load(deps.storage, pair)?,
+        )?),
+        QueryMsg::GetTwapConfig { pair } => Ok(to_json_binary(&get_twap_config(deps, &pair)?)?),
+        QueryMsg::GetRole { role, addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+
+            Ok(to_json_binary(
+                &role.members().may_load(deps.storage, addr)?,
+            )?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => {
+            let start_after = start_after
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?;
+            let members = role
+                .members()
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(50) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&members)?)
+        }
         QueryMsg::GetConfig => {
             let config = CONFIG.load(deps.storage)?;
             Ok(to_json_binary(&config)?)
@@ -2,6 +2,8 @@ use auction_package::{error::AuctionError, Pair};
 use cosmwasm_std::{CheckedFromRatioError, DecimalRangeExceeded, OverflowError, StdError};
 use thiserror::Error;
 
+use crate::state::Role;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -51,4 +53,40 @@ pub enum ContractError {
 
     #[error("No astroport path found for pair: {0}")]
     NoAstroPath(Pair),
+
+    #[error("No price source found for pair: {0}")]
+    PriceSourceNotFound(Pair),
+
+    #[error("Price source doesn't match the pair")]
+    PriceSourceIsWrong,
+
+    #[error("TWAP window and max prices must be more than 0")]
+    InvalidTwapConfig,
+
+    #[error("Price sources must not be empty")]
+    PriceSourcesEmpty,
+
+    #[error("Quorum must be more than 0 and not more than the amount of sources")]
+    InvalidPriceQuorum,
+
+    #[error("Trim percentage must be less than 50%")]
+    InvalidTrimPerc,
+
+    #[error("Only {live} price sources are live, but quorum is {quorum}")]
+    PriceQuorumNotReached { live: usize, quorum: u32 },
+
+    #[error("No pending price found for pair: {0}")]
+    PendingPriceNotFound(Pair),
+
+    #[error("Sender doesn't have the {0} role for pair: {1}")]
+    MissingRole(Role, Pair),
+
+    #[error("Address doesn't have the {0} role")]
+    RoleNotFound(Role),
+
+    #[error("Limit must be more than 0")]
+    LimitIsZero,
+
+    #[error("No price found for pair: {0}")]
+    PriceNotFound(Pair),
 }
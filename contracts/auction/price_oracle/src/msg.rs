@@ -1,9 +1,12 @@
-use auction_package::{Pair, Price};
+use auction_package::{helpers::GetPriceResponse, Pair, Price};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::{Addr, Decimal, Timestamp};
 use cw_utils::Expiration;
 
-use crate::state::{Config, PriceStep};
+use crate::{
+    sources::PriceSources,
+    state::{Config, PendingPrice, PriceHistoryEntry, PriceStep, Role, RoleScope, TwapConfig},
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -21,6 +24,12 @@ pub enum ExecuteMsg {
     UpdatePrice {
         pair: Pair,
     },
+    /// Update the prices of the given pairs, or of the next `limit` pairs of the auctions manager.
+    /// A pair that fails to update doesn't fail the batch.
+    UpdatePrices {
+        pairs: Option<Vec<Pair>>,
+        limit: Option<u32>,
+    },
     AddAstroPath {
         pair: Pair,
         path: Vec<PriceStep>,
@@ -29,10 +38,45 @@ pub enum ExecuteMsg {
         pair: Pair,
         path: Vec<PriceStep>,
     },
+    /// Set the price sources of the pair, used instead of the astroport path
+    SetPriceSources {
+        pair: Pair,
+        sources: PriceSources,
+    },
+    RemovePriceSources {
+        pair: Pair,
+    },
+    /// Update how the average price of the pair is calculated
+    UpdateTwapConfig {
+        pair: Pair,
+        config: TwapConfig,
+    },
     UpdateConfig {
         auction_manager_addr: Option<String>,
         seconds_allow_manual_change: Option<u64>,
         seconds_auction_prices_fresh: Option<u64>,
+        /// 0 disables the price deviation check
+        max_price_deviation_bps: Option<u64>,
+        /// Empty string removes the anchor denom
+        anchor_denom: Option<String>,
+    },
+    /// Publish the pending price of the pair
+    ApprovePendingPrice {
+        pair: Pair,
+    },
+    /// Remove the pending price of the pair
+    RejectPendingPrice {
+        pair: Pair,
+    },
+    /// Give the address a role, replaces the scope if the address already has the role
+    AddRole {
+        role: Role,
+        addr: String,
+        pairs: Option<Vec<Pair>>,
+    },
+    RemoveRole {
+        role: Role,
+        addr: String,
     },
     StartAdminChange {
         addr: String,
@@ -46,8 +90,13 @@ pub enum ExecuteMsg {
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     /// Get the minimum amount users can auction
-    #[returns(Price)]
+    /// If we don't have the price of the pair, it's derived from the inverse pair,
+    /// or from the prices of both denoms against the anchor denom
+    #[returns(GetPriceResponse)]
     GetPrice { pair: Pair },
+    /// Same as `GetPrice`, but also returns if the price was derived
+    #[returns(PriceDetailsResponse)]
+    GetPriceDetails { pair: Pair },
     #[returns(Vec<Price>)]
     GetLocalPrice { pair: Pair },
     #[returns(Vec<(Pair, Price)>)]
@@ -55,12 +104,82 @@ pub enum QueryMsg {
         from: Option<Pair>,
         limit: Option<u32>,
     },
+    /// Every price published for the pair with its source, ordered by id from oldest to newest
+    #[returns(Vec<(u64, PriceHistoryEntry)>)]
+    GetPriceHistory {
+        pair: Pair,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the prices that were not updated in the last `max_age_seconds`,
+    /// `limit` is the number of prices checked
+    #[returns(StalePricesResponse)]
+    GetStalePrices {
+        max_age_seconds: u64,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    },
+    /// Get the pairs of the auctions manager that need attention,
+    /// `limit` is the number of pairs checked
+    #[returns(HealthResponse)]
+    GetHealth {
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    },
+    #[returns(PriceSources)]
+    GetPriceSources { pair: Pair },
+    #[returns(TwapConfig)]
+    GetTwapConfig { pair: Pair },
+    #[returns(Option<PendingPrice>)]
+    GetPendingPrice { pair: Pair },
+    #[returns(Option<RoleScope>)]
+    GetRole { role: Role, addr: String },
+    #[returns(Vec<(Addr, RoleScope)>)]
+    GetRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     #[returns(Config)]
     GetConfig,
     #[returns(Addr)]
     GetAdmin,
 }
 
+#[cw_serde]
+pub struct PriceDetailsResponse {
+    pub price: Decimal,
+    pub time: Timestamp,
+    /// True if the price is derived from the prices of other pairs
+    pub derived: bool,
+}
+
+#[cw_serde]
+pub struct StalePricesResponse {
+    /// The prices on this page that were not updated in the last `max_age_seconds`
+    pub prices: Vec<(Pair, Price)>,
+    /// The last pair checked on this page, use it as `start_after` to get the next page.
+    /// None when there are no more prices to check
+    pub last_checked: Option<Pair>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct HealthResponse {
+    /// Pairs we don't have a price for
+    pub no_price: Vec<Pair>,
+    /// Pairs without an astroport path or price sources, updating them fails with `NoAstroPath`
+    /// unless the auction prices can be used
+    pub no_astro_path: Vec<Pair>,
+    /// Pairs whose auction doesn't have enough fresh prices to be used as the price
+    pub auction_twap_too_short: Vec<Pair>,
+    /// Pairs that were not updated for `seconds_allow_manual_change`, so can be updated manually
+    pub past_manual_change: Vec<Pair>,
+    /// The last pair checked on this page, use it as `start_after` to get the next page.
+    /// None when there are no more pairs to check
+    pub last_checked: Option<Pair>,
+}
+
 #[cw_serde]
 pub enum MigrateMsg {
     NoStateChange {},
@@ -0,0 +1,556 @@
+use auction_package::{helpers::GetPriceResponse, Pair, Price};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Deps, DepsMut, Env, Int64, Timestamp, Uint128, Uint64};
+
+use crate::{
+    error::ContractError,
+    state::{AstroPriceQuery, CumulativePrice, PriceStep, ASTRO_CUMULATIVE_PRICES},
+};
+
+/// The default amount of pair.0 we simulate swaps with
+const SIMULATION_AMOUNT: u128 = 1_000_000;
+/// Astroport cumulative prices are the sum of price * 10^6 per second
+const TWAP_PRECISION: u32 = 6;
+
+/// How the prices of multiple sources are aggregated into a single price
+#[cw_serde]
+#[derive(Default)]
+pub enum PriceAggregation {
+    #[default]
+    Median,
+    /// Mean of the prices, after removing `trim_perc` of the highest and lowest prices
+    TrimmedMean { trim_perc: Decimal },
+}
+
+/// The price sources of a pair, used when auction prices are not fresh
+#[cw_serde]
+pub struct PriceSources {
+    pub sources: Vec<PriceSource>,
+    pub aggregation: PriceAggregation,
+    /// Prices that deviate from the median of all live sources by more than this are rejected
+    pub max_deviation: Decimal,
+    /// Minimum amount of sources with a valid price we need to update the price
+    pub quorum: u32,
+    /// Prices older than this amount of seconds are not considered live
+    pub max_price_age: Option<u64>,
+}
+
+impl PriceSources {
+    pub fn name(&self) -> &str {
+        match (self.sources.as_slice(), &self.aggregation) {
+            ([source], _) => source.name(),
+            (_, PriceAggregation::Median) => "median",
+            (_, PriceAggregation::TrimmedMean { .. }) => "trimmed-mean",
+        }
+    }
+
+    pub fn verify(&self, deps: Deps, env: &Env, pair: &Pair) -> Result<(), ContractError> {
+        if self.sources.is_empty() {
+            return Err(ContractError::PriceSourcesEmpty);
+        }
+
+        if self.quorum == 0 || self.quorum as usize > self.sources.len() {
+            return Err(ContractError::InvalidPriceQuorum);
+        }
+
+        if let PriceAggregation::TrimmedMean { trim_perc } = self.aggregation {
+            if trim_perc >= Decimal::percent(50) {
+                return Err(ContractError::InvalidTrimPerc);
+            }
+        }
+
+        self.sources
+            .iter()
+            .try_for_each(|source| source.verify(deps, env, pair))
+    }
+
+    /// Query all sources and aggregate their prices into a single price,
+    /// sources that fail to return a price are ignored as long as we have a quorum.
+    pub fn query_price(&self, deps: Deps, env: &Env, pair: &Pair) -> Result<Price, ContractError> {
+        let live_prices: Vec<Price> = self
+            .sources
+            .iter()
+            .filter_map(|source| source.query_price(deps, env, pair).ok())
+            .filter(|price| {
+                let is_fresh = match self.max_price_age {
+                    Some(max_age) => price.time.seconds() + max_age >= env.block.time.seconds(),
+                    None => true,
+                };
+                is_fresh && !price.price.is_zero()
+            })
+            .collect();
+        self.verify_quorum(live_prices.len())?;
+
+        // Reject prices that are too far from the median
+        let median = get_median(&sorted_prices(&live_prices));
+        let prices: Vec<Price> = live_prices
+            .into_iter()
+            .filter(|price| {
+                let diff = if price.price > median {
+                    price.price - median
+                } else {
+                    median - price.price
+                };
+                diff <= median * self.max_deviation
+            })
+            .collect();
+        self.verify_quorum(prices.len())?;
+
+        let sorted = sorted_prices(&prices);
+        let price = match self.aggregation {
+            PriceAggregation::Median => get_median(&sorted),
+            PriceAggregation::TrimmedMean { trim_perc } => {
+                let trim = (Decimal::from_atomics(sorted.len() as u128, 0)? * trim_perc)
+                    .to_uint_floor()
+                    .u128() as usize;
+                let trimmed = &sorted[trim..sorted.len() - trim];
+
+                trimmed
+                    .iter()
+                    .sum::<Decimal>()
+                    .checked_div(Decimal::from_atomics(trimmed.len() as u128, 0)?)?
+            }
+        };
+
+        // The price is only as fresh as the oldest price we used
+        let time = prices
+            .iter()
+            .map(|price| price.time)
+            .min()
+            .unwrap_or(env.block.time);
+
+        Ok(Price { price, time })
+    }
+
+    /// The astroport steps of all the sources
+    pub fn astro_steps(&self) -> impl Iterator<Item = &PriceStep> {
+        self.sources.iter().flat_map(|source| match source {
+            PriceSource::Astroport { path } => path.as_slice(),
+            _ => &[],
+        })
+    }
+
+    fn verify_quorum(&self, live: usize) -> Result<(), ContractError> {
+        if live < self.quorum as usize {
+            return Err(ContractError::PriceQuorumNotReached {
+                live,
+                quorum: self.quorum,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn sorted_prices(prices: &[Price]) -> Vec<Decimal> {
+    let mut sorted: Vec<Decimal> = prices.iter().map(|price| price.price).collect();
+    sorted.sort();
+    sorted
+}
+
+/// Median of a sorted non empty list
+fn get_median(sorted: &[Decimal]) -> Decimal {
+    let mid = sorted.len() / 2;
+
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / Decimal::from_ratio(2_u128, 1_u128)
+    } else {
+        sorted[mid]
+    }
+}
+
+/// A single source of price for a pair
+#[cw_serde]
+pub enum PriceSource {
+    /// Price over a path of astroport pools
+    Astroport { path: Vec<PriceStep> },
+    /// Osmosis style pool contract that returns the spot price of 2 denoms
+    OsmosisPool { pool_address: Addr },
+    /// Any pair contract that supports the `Simulation` query,
+    /// if `is_cw20` is true, pair.0 is the address of a CW20 token.
+    /// `offer_amount` is the amount of pair.0 we simulate the swap with, defaults to 1_000_000,
+    /// should be set to 10^decimals of pair.0 to price a single token.
+    Simulation {
+        pool_address: Addr,
+        is_cw20: bool,
+        offer_amount: Option<Uint128>,
+    },
+    /// Pyth / Slinky style price feed contract
+    PriceFeed {
+        contract_addr: Addr,
+        feed_id: String,
+    },
+    /// Another oracle contract that supports our `GetPrice` query
+    Oracle { contract_addr: Addr },
+}
+
+impl PriceSource {
+    pub fn name(&self) -> &str {
+        match self {
+            PriceSource::Astroport { .. } => "astroport",
+            PriceSource::OsmosisPool { .. } => "osmosis-pool",
+            PriceSource::Simulation { .. } => "simulation",
+            PriceSource::PriceFeed { .. } => "price-feed",
+            PriceSource::Oracle { .. } => "oracle",
+        }
+    }
+
+    pub fn verify(&self, deps: Deps, env: &Env, pair: &Pair) -> Result<(), ContractError> {
+        match self {
+            PriceSource::Astroport { path } => verify_astro_path(pair, path),
+            PriceSource::OsmosisPool { pool_address } => {
+                deps.api.addr_validate(pool_address.as_str())?;
+                Ok(())
+            }
+            PriceSource::Simulation {
+                pool_address,
+                is_cw20,
+                offer_amount,
+            } => {
+                deps.api.addr_validate(pool_address.as_str())?;
+
+                if *offer_amount == Some(Uint128::zero()) {
+                    return Err(ContractError::PriceSourceIsWrong);
+                }
+
+                if *is_cw20 {
+                    deps.api
+                        .addr_validate(&pair.0)
+                        .map_err(|_| ContractError::PriceSourceIsWrong)?;
+                }
+                Ok(())
+            }
+            PriceSource::PriceFeed {
+                contract_addr,
+                feed_id,
+            } => {
+                deps.api.addr_validate(contract_addr.as_str())?;
+
+                if feed_id.is_empty() {
+                    return Err(ContractError::PriceSourceIsWrong);
+                }
+                Ok(())
+            }
+            PriceSource::Oracle { contract_addr } => {
+                deps.api.addr_validate(contract_addr.as_str())?;
+
+                // Querying ourselves will just return our own price
+                if contract_addr == env.contract.address {
+                    return Err(ContractError::PriceSourceIsWrong);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Query the price of the pair from the source
+    pub fn query_price(&self, deps: Deps, env: &Env, pair: &Pair) -> Result<Price, ContractError> {
+        match self {
+            PriceSource::Astroport { path } => get_price_from_astroport(deps, env, path),
+            PriceSource::OsmosisPool { pool_address } => {
+                let res: SpotPriceResponse = deps.querier.query_wasm_smart(
+                    pool_address,
+                    &OsmosisPoolQueryMsg::SpotPrice {
+                        quote_asset_denom: pair.1.clone(),
+                        base_asset_denom: pair.0.clone(),
+                    },
+                )?;
+
+                Ok(Price {
+                    price: res.spot_price,
+                    time: env.block.time,
+                })
+            }
+            PriceSource::Simulation {
+                pool_address,
+                is_cw20,
+                offer_amount,
+            } => {
+                let offer_amount = offer_amount.unwrap_or(Uint128::new(SIMULATION_AMOUNT));
+                let info = if *is_cw20 {
+                    astroport::asset::AssetInfo::Token {
+                        contract_addr: Addr::unchecked(&pair.0),
+                    }
+                } else {
+                    astroport::asset::AssetInfo::NativeToken {
+                        denom: pair.0.clone(),
+                    }
+                };
+
+                let res: SimulationResponse = deps.querier.query_wasm_smart(
+                    pool_address,
+                    &PairQueryMsg::Simulation {
+                        offer_asset: astroport::asset::Asset {
+                            info,
+                            amount: offer_amount,
+                        },
+                    },
+                )?;
+
+                // Only the commission is added back, the spread of swapping `offer_amount`
+                // stays deducted, so the price is the rate we would get for that amount
+                let return_amount = res.return_amount.checked_add(res.commission_amount)?;
+
+                Ok(Price {
+                    price: Decimal::checked_from_ratio(return_amount, offer_amount)?,
+                    time: env.block.time,
+                })
+            }
+            PriceSource::PriceFeed {
+                contract_addr,
+                feed_id,
+            } => {
+                let res: PriceFeedResponse = deps.querier.query_wasm_smart(
+                    contract_addr,
+                    &PriceFeedQueryMsg::PriceFeed {
+                        id: feed_id.clone(),
+                    },
+                )?;
+
+                res.price_feed.price.to_price()
+            }
+            PriceSource::Oracle { contract_addr } => {
+                let res: GetPriceResponse = deps.querier.query_wasm_smart(
+                    contract_addr,
+                    &crate::msg::QueryMsg::GetPrice { pair: pair.clone() },
+                )?;
+
+                Ok(Price {
+                    price: res.price,
+                    time: res.time,
+                })
+            }
+        }
+    }
+}
+
+pub(crate) fn verify_astro_path(pair: &Pair, path: &[PriceStep]) -> Result<(), ContractError> {
+    if path.is_empty() {
+        return Err(ContractError::PricePathIsEmpty);
+    }
+
+    if path[0].denom1 != pair.0 || path[path.len() - 1].denom2 != pair.1 {
+        return Err(ContractError::PricePathIsWrong);
+    }
+
+    if path
+        .iter()
+        .any(|step| step.offer_amount == Some(Uint128::zero()))
+    {
+        return Err(ContractError::PricePathIsWrong);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn get_price_from_astroport(
+    deps: Deps,
+    env: &Env,
+    steps: &[PriceStep],
+) -> Result<Price, ContractError> {
+    // Every step is priced on its own, the price of the path is the product of the steps
+    let price = steps.iter().try_fold(Decimal::one(), |price, step| {
+        Ok::<_, ContractError>(price.checked_mul(get_astro_step_price(deps, env, step)?)?)
+    })?;
+
+    Ok(Price {
+        price,
+        time: env.block.time,
+    })
+}
+
+/// Get the price of denom1 in denom2 from the pool of the step
+fn get_astro_step_price(deps: Deps, env: &Env, step: &PriceStep) -> Result<Decimal, ContractError> {
+    match step.price_query.clone().unwrap_or_default() {
+        price_query
+        @ (AstroPriceQuery::Simulation | AstroPriceQuery::SimulationExcludingSpread) => {
+            let offer_amount = step.offer_amount.unwrap_or(Uint128::new(SIMULATION_AMOUNT));
+
+            let res = astroport::querier::simulate(
+                &deps.querier,
+                step.pool_address.clone(),
+                &astroport::asset::Asset {
+                    info: native_asset_info(&step.denom1),
+                    amount: offer_amount,
+                },
+            )?;
+
+            let mut return_amount = res.return_amount.checked_add(res.commission_amount)?;
+
+            // When we exclude the spread, the spread of swapping `offer_amount` stays deducted,
+            // so the price is the rate we would get for that amount
+            if price_query == AstroPriceQuery::Simulation {
+                return_amount = return_amount.checked_add(res.spread_amount)?;
+            }
+
+            Ok(Decimal::checked_from_ratio(return_amount, offer_amount)?)
+        }
+        AstroPriceQuery::Spot => get_astro_spot_price(deps, step),
+        AstroPriceQuery::Cumulative => {
+            let last_cumulative = ASTRO_CUMULATIVE_PRICES.may_load(
+                deps.storage,
+                (step.pool_address.clone(), step.denom1.clone()),
+            )?;
+
+            match last_cumulative {
+                Some(last_cumulative) if last_cumulative.time < env.block.time => {
+                    let cumulative = query_astro_cumulative_price(deps, step)?;
+                    let seconds = env.block.time.seconds() - last_cumulative.time.seconds();
+
+                    // The cumulative price can overflow, so we wrap like the pool does
+                    Ok(Decimal::checked_from_ratio(
+                        cumulative.wrapping_sub(last_cumulative.cumulative),
+                        Uint128::from(seconds)
+                            .checked_mul(Uint128::new(10_u128.pow(TWAP_PRECISION)))?,
+                    )?)
+                }
+                _ => get_astro_spot_price(deps, step),
+            }
+        }
+    }
+}
+
+/// The ratio of the reserves of the pool
+fn get_astro_spot_price(deps: Deps, step: &PriceStep) -> Result<Decimal, ContractError> {
+    let res: astroport::pair::PoolResponse = deps
+        .querier
+        .query_wasm_smart(&step.pool_address, &astroport::pair::QueryMsg::Pool {})?;
+
+    let get_reserve = |denom: &str| {
+        res.assets
+            .iter()
+            .find(|asset| asset.info == native_asset_info(denom))
+            .map(|asset| asset.amount)
+            .ok_or(ContractError::PricePathIsWrong)
+    };
+
+    Ok(Decimal::checked_from_ratio(
+        get_reserve(&step.denom2)?,
+        get_reserve(&step.denom1)?,
+    )?)
+}
+
+fn query_astro_cumulative_price(deps: Deps, step: &PriceStep) -> Result<Uint128, ContractError> {
+    let res: astroport::pair::CumulativePricesResponse = deps.querier.query_wasm_smart(
+        &step.pool_address,
+        &astroport::pair::QueryMsg::CumulativePrices {},
+    )?;
+
+    res.cumulative_prices
+        .into_iter()
+        .find(|(offer, ask, _)| {
+            *offer == native_asset_info(&step.denom1) && *ask == native_asset_info(&step.denom2)
+        })
+        .map(|(_, _, cumulative)| cumulative)
+        .ok_or(ContractError::PricePathIsWrong)
+}
+
+/// Save the current cumulative prices of the steps that use them,
+/// so the next update can calculate the average price since this update.
+pub(crate) fn save_astro_cumulative_prices<'a>(
+    deps: DepsMut,
+    env: &Env,
+    steps: impl Iterator<Item = &'a PriceStep>,
+) -> Result<(), ContractError> {
+    for step in steps.filter(|step| step.price_query == Some(AstroPriceQuery::Cumulative)) {
+        // A pool that fails to answer is not used for the price either, so we can skip it
+        if let Ok(cumulative) = query_astro_cumulative_price(deps.as_ref(), step) {
+            ASTRO_CUMULATIVE_PRICES.save(
+                deps.storage,
+                (step.pool_address.clone(), step.denom1.clone()),
+                &CumulativePrice {
+                    cumulative,
+                    time: env.block.time,
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn native_asset_info(denom: &str) -> astroport::asset::AssetInfo {
+    astroport::asset::AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    }
+}
+
+/// Query of osmosis style pools
+#[cw_serde]
+pub enum OsmosisPoolQueryMsg {
+    SpotPrice {
+        quote_asset_denom: String,
+        base_asset_denom: String,
+    },
+}
+
+#[cw_serde]
+pub struct SpotPriceResponse {
+    pub spot_price: Decimal,
+}
+
+/// Query of pair contracts that support simulating a swap
+#[cw_serde]
+pub enum PairQueryMsg {
+    Simulation {
+        offer_asset: astroport::asset::Asset,
+    },
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+/// Query of Pyth / Slinky style price feed contracts
+#[cw_serde]
+pub enum PriceFeedQueryMsg {
+    PriceFeed { id: String },
+}
+
+#[cw_serde]
+pub struct PriceFeedResponse {
+    pub price_feed: PriceFeed,
+}
+
+#[cw_serde]
+pub struct PriceFeed {
+    pub id: String,
+    pub price: FeedPrice,
+    pub ema_price: FeedPrice,
+}
+
+/// The price is `price * 10^expo`
+#[cw_serde]
+pub struct FeedPrice {
+    pub price: Int64,
+    pub conf: Uint64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl FeedPrice {
+    pub fn to_price(&self) -> Result<Price, ContractError> {
+        if self.price <= Int64::zero() {
+            return Err(ContractError::PriceIsZero);
+        }
+
+        let amount = self.price.i64() as u128;
+        let price = if self.expo <= 0 {
+            Decimal::from_atomics(amount, self.expo.unsigned_abs())?
+        } else {
+            Decimal::from_atomics(amount, 0)?.checked_mul(Decimal::from_atomics(
+                10_u128
+                    .checked_pow(self.expo as u32)
+                    .ok_or(ContractError::PriceSourceIsWrong)?,
+                0,
+            )?)?
+        };
+
+        Ok(Price {
+            price,
+            time: Timestamp::from_seconds(self.publish_time.max(0) as u64),
+        })
+    }
+}
@@ -1,14 +1,34 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt};
 
 use auction_package::{Pair, Price};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
+use crate::sources::PriceSources;
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const ASTRO_PRICE_PATHS: Map<Pair, Vec<PriceStep>> = Map::new("astro_price_paths");
-/// Local last 10 prices to be calculated for the average
+/// Price sources per pair, used instead of the astroport path when set
+pub const PRICE_SOURCES: Map<Pair, PriceSources> = Map::new("price_sources");
+/// Local last prices to be calculated for the average
 pub const LOCAL_PRICES: Map<Pair, VecDeque<Price>> = Map::new("local_prices");
+/// How the average price is calculated per pair, uses the default if not set
+pub const TWAP_CONFIGS: Map<Pair, TwapConfig> = Map::new("twap_configs");
+/// New prices that deviate too much from the current price, waiting to be confirmed
+pub const PENDING_PRICES: Map<Pair, PendingPrice> = Map::new("pending_prices");
+/// Every published price per (pair, id), ids are incremented per pair
+pub const PRICE_HISTORY: Map<(Pair, u64), PriceHistoryEntry> = Map::new("price_history");
+/// The last pair `UpdatePrices` updated, the next batch continues from it
+pub const UPDATE_PRICES_CURSOR: Item<Pair> = Item::new("update_prices_cursor");
+/// Addresses allowed to call `UpdatePrice`
+pub const UPDATERS: Map<Addr, RoleScope> = Map::new("updaters");
+/// Addresses allowed to call `ManualPriceUpdate`
+pub const MANUAL_PRICE_SIGNERS: Map<Addr, RoleScope> = Map::new("manual_price_signers");
+/// Last seen cumulative price of astroport pools per (pool, offer denom),
+/// used to calculate the average price between 2 updates
+pub const ASTRO_CUMULATIVE_PRICES: Map<(Addr, String), CumulativePrice> =
+    Map::new("astro_cumulative_prices");
 
 #[cw_serde]
 pub struct Config {
@@ -19,6 +39,76 @@ pub struct Config {
     /// The amount of seconds we use auctions as our price source
     /// If last auction ran more than this amount of seconds, we do not use the auction as the source of price
     pub seconds_auction_prices_fresh: u64,
+    /// New prices that deviate from the current price by more than this amount of bps
+    /// are held as pending until confirmed by another update or approved by the admin
+    pub max_price_deviation_bps: Option<u64>,
+    /// Prices of pairs we don't have a price for are derived through this denom
+    pub anchor_denom: Option<String>,
+}
+
+#[cw_serde]
+pub enum Role {
+    /// Can update prices, if no address has this role, anyone can update prices
+    Updater,
+    /// Can update prices manually
+    ManualPriceSigner,
+}
+
+impl Role {
+    pub fn members(&self) -> Map<'static, Addr, RoleScope> {
+        match self {
+            Role::Updater => UPDATERS,
+            Role::ManualPriceSigner => MANUAL_PRICE_SIGNERS,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Updater => write!(f, "updater"),
+            Role::ManualPriceSigner => write!(f, "manual-price-signer"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RoleScope {
+    /// The pairs the role is allowed for, all pairs if None
+    pub pairs: Option<Vec<Pair>>,
+}
+
+#[cw_serde]
+pub struct PendingPrice {
+    pub price: Price,
+    pub source: String,
+}
+
+#[cw_serde]
+pub struct PriceHistoryEntry {
+    /// The price we got from the source
+    pub price: Decimal,
+    /// The average price after adding this price
+    pub average_price: Decimal,
+    pub time: Timestamp,
+    pub source: String,
+}
+
+#[cw_serde]
+pub struct TwapConfig {
+    /// The amount of seconds back from now we average the local prices over
+    pub window_seconds: u64,
+    /// The max amount of local prices we keep
+    pub max_prices: u32,
+}
+
+impl Default for TwapConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: 60 * 60 * 24 * 3, // 3 days
+            max_prices: 10,
+        }
+    }
 }
 
 #[cw_serde]
@@ -26,4 +116,32 @@ pub struct PriceStep {
     pub denom1: String,
     pub denom2: String,
     pub pool_address: Addr,
+    /// The amount of denom1 we simulate the swap with, defaults to 1_000_000.
+    /// Should be set to 10^decimals of denom1 to price a single token.
+    pub offer_amount: Option<Uint128>,
+    /// How we query the price of denom1 from the pool, defaults to `Simulation`
+    pub price_query: Option<AstroPriceQuery>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum AstroPriceQuery {
+    /// Simulate a swap of `offer_amount`, the commission and spread are added back to the
+    /// return amount, so the price doesn't depend on the size of the swap
+    #[default]
+    Simulation,
+    /// Simulate a swap of `offer_amount`, only the commission is added back,
+    /// so the price includes the spread of a swap of that size
+    SimulationExcludingSpread,
+    /// The ratio of the pool reserves, only correct for constant product (xyk) pools
+    Spot,
+    /// The average price since the last update from the cumulative prices of the pool,
+    /// uses the spot price when we don't have a previous cumulative price
+    Cumulative,
+}
+
+#[cw_serde]
+pub struct CumulativePrice {
+    pub cumulative: Uint128,
+    pub time: Timestamp,
 }
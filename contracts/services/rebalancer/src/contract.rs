@@ -19,6 +19,7 @@ use valence_package::services::rebalancer::{
 use valence_package::states::{QueryFeeAction, ADMIN, SERVICES_MANAGER, SERVICE_FEE_CONFIG};
 
 use crate::error::ContractError;
+use crate::helpers::{query_cw20_denoms, query_denom_balance};
 use crate::msg::{InstantiateMsg, ManagersAddrsResponse, MigrateMsg, QueryMsg, WhitelistsResponse};
 use crate::rebalance::execute_system_rebalance;
 use crate::state::{
@@ -142,6 +143,16 @@ pub fn execute(
             let mut has_min_balance = false;
             let mut min_value_is_met = false;
             let mut total_value = Uint128::zero();
+            let cw20_denoms = query_cw20_denoms(
+                deps.as_ref(),
+                &auctions_manager_addr,
+                &base_denom.denom,
+                &data
+                    .targets
+                    .iter()
+                    .map(|target| target.denom.as_str())
+                    .collect::<Vec<&str>>(),
+            )?;
 
             for target in data.targets.clone() {
                 if !(1..=9999).contains(&target.bps) {
@@ -165,7 +176,8 @@ pub fn execute(
                 }
 
                 // Calculate value of the target and make sure we have the minimum value required
-                let curr_balance = deps.querier.query_balance(&registree, &target.denom)?;
+                let curr_balance =
+                    query_denom_balance(deps.as_ref(), &registree, &target.denom, &cw20_denoms)?;
 
                 if !min_value_is_met {
                     let value = if target.denom == base_denom.denom {
@@ -448,9 +460,21 @@ pub fn execute(
 
             let mut total_value = Uint128::zero();
             let mut min_value_met = false;
+            let cw20_denoms = query_cw20_denoms(
+                deps.as_ref(),
+                &auctions_manager_addr,
+                &base_denom.denom,
+                &paused_data
+                    .config
+                    .targets
+                    .iter()
+                    .map(|target| target.denom.as_str())
+                    .collect::<Vec<&str>>(),
+            )?;
 
             for target in &paused_data.config.targets {
-                let target_balance = deps.querier.query_balance(&account, &target.denom)?;
+                let target_balance =
+                    query_denom_balance(deps.as_ref(), &account, &target.denom, &cw20_denoms)?;
 
                 let value = if target.denom == base_denom.denom {
                     target_balance.amount
@@ -111,4 +111,7 @@ pub enum ContractError {
 
     #[error("Cannot find pair address: {0} / {1}")]
     PairDoesntExists(String, String),
+
+    #[error("Pair is deprecated: {0} / {1}")]
+    PairIsDeprecated(String, String),
 }
@@ -1,5 +1,8 @@
+use auction::state::AUCTION_CONFIG;
+use auction_package::{states::PAIRS, Pair};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, SubMsg, Uint128};
+use cosmwasm_std::{coin, Addr, Coin, Decimal, Deps, StdResult, SubMsg, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
 use serde::Serialize;
 use valence_package::{
     event_indexing::ValenceGenericEvent,
@@ -10,6 +13,55 @@ pub const TRADE_HARD_LIMIT: Decimal = Decimal::raw(5_u128);
 
 pub(crate) type TradesTuple = (Vec<TargetHelper>, Vec<TargetHelper>);
 
+/// Query which of the denoms are CW20 tokens, from the config of the auctions
+/// of the base denom to the other denoms, denoms without an auction are native
+pub fn query_cw20_denoms(
+    deps: Deps,
+    auctions_manager_addr: &Addr,
+    base_denom: &str,
+    denoms: &[&str],
+) -> StdResult<Vec<String>> {
+    let mut cw20_denoms: Vec<String> = vec![];
+
+    for denom in denoms.iter().filter(|denom| **denom != base_denom) {
+        let pair = Pair::from((base_denom.to_string(), denom.to_string()));
+        let Some(pair_addr) = PAIRS.query(&deps.querier, auctions_manager_addr.clone(), pair)?
+        else {
+            continue;
+        };
+
+        for cw20_denom in AUCTION_CONFIG.query(&deps.querier, pair_addr)?.cw20_denoms {
+            if !cw20_denoms.contains(&cw20_denom) {
+                cw20_denoms.push(cw20_denom);
+            }
+        }
+    }
+
+    Ok(cw20_denoms)
+}
+
+/// Query the balance of a denom, CW20 tokens are queried from the token contract,
+/// native denoms are queried from the bank
+pub fn query_denom_balance(
+    deps: Deps,
+    addr: &Addr,
+    denom: &str,
+    cw20_denoms: &[String],
+) -> StdResult<Coin> {
+    if !cw20_denoms.iter().any(|cw20_denom| cw20_denom == denom) {
+        return deps.querier.query_balance(addr, denom);
+    }
+
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        denom,
+        &Cw20QueryMsg::Balance {
+            address: addr.to_string(),
+        },
+    )?;
+
+    Ok(coin(balance.balance.u128(), denom))
+}
+
 /// Helper struct for our calculation,
 /// it holds the target as well as price, balance, input and the amount we need to trade
 #[derive(Debug, Clone)]
@@ -33,7 +85,7 @@ pub struct TargetHelper {
 #[cw_serde]
 pub struct RebalanceResponse<E: Serialize> {
     pub config: RebalancerConfig,
-    pub msg: Option<SubMsg>,
+    pub msgs: Vec<SubMsg>,
     pub event: ValenceGenericEvent<E>,
     pub should_pause: bool,
 }
@@ -41,13 +93,13 @@ pub struct RebalanceResponse<E: Serialize> {
 impl<E: Serialize> RebalanceResponse<E> {
     pub fn new(
         config: RebalancerConfig,
-        msg: Option<SubMsg>,
+        msgs: Vec<SubMsg>,
         event: ValenceGenericEvent<E>,
         should_pause: bool,
     ) -> Self {
         Self {
             config,
-            msg,
+            msgs,
             event,
             should_pause,
         }
@@ -1,14 +1,16 @@
 use std::{borrow::BorrowMut, collections::HashMap, str::FromStr};
 
+use auction::state::AUCTION_CONFIG;
 use auction_package::{
     helpers::GetPriceResponse,
-    states::{MinAmount, MIN_AUCTION_AMOUNT, PAIRS},
+    states::{MinAmount, DEPRECATED_PAIRS, MIN_AUCTION_AMOUNT, PAIRS},
     Pair,
 };
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event, Order,
-    Response, SignedDecimal, StdError, SubMsg, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event,
+    Order, Response, SignedDecimal, StdError, SubMsg, Uint128, WasmMsg,
 };
+use cw20::Cw20ExecuteMsg;
 use cw_storage_plus::Bound;
 use valence_package::{
     event_indexing::ValenceEvent,
@@ -24,7 +26,9 @@ use valence_package::{
 use crate::{
     contract::{DEFAULT_SYSTEM_LIMIT, REPLY_DEFAULT_REBALANCE},
     error::ContractError,
-    helpers::{RebalanceResponse, TargetHelper, TradesTuple},
+    helpers::{
+        query_cw20_denoms, query_denom_balance, RebalanceResponse, TargetHelper, TradesTuple,
+    },
     state::{
         AUCTIONS_MANAGER_ADDR, BASE_DENOM_WHITELIST, CONFIGS, CYCLE_PERIOD, DENOM_WHITELIST,
         PAUSED_CONFIGS, SYSTEM_REBALANCE_STATUS,
@@ -154,7 +158,7 @@ pub fn execute_system_rebalance(
         );
         let Ok(RebalanceResponse {
             config,
-            msg,
+            msgs: account_msgs,
             event,
             should_pause,
         }) = rebalance_res
@@ -185,9 +189,7 @@ pub fn execute_system_rebalance(
         // Add event to all events
         account_events.push(event.into());
 
-        if let Some(msg) = msg {
-            msgs.push(msg);
-        }
+        msgs.extend(account_msgs);
     }
 
     // We checked if we finished looping over all accounts or not
@@ -247,7 +249,8 @@ pub fn do_rebalance(
     cycle_period: u64,
 ) -> Result<RebalanceResponse<Empty>, ContractError> {
     // get a vec of inputs for our calculations
-    let (total_value, mut target_helpers) = get_inputs(deps, account, &config, prices)?;
+    let (total_value, mut target_helpers) =
+        get_inputs(deps, account, auction_manager, &config, prices)?;
 
     // Get required minim
     let min_value = *min_values
@@ -261,7 +264,7 @@ pub fn do_rebalance(
         };
 
         // We pause the account if the account balance doesn't meet the minimum requirements
-        return Ok(RebalanceResponse::new(config, None, event, true));
+        return Ok(RebalanceResponse::new(config, vec![], event, true));
     };
 
     // Verify the targets, if we have a min_balance we need to do some extra steps
@@ -311,43 +314,63 @@ pub fn do_rebalance(
     // is independent of other trade msg
     // This means 1 trade might fail while another pass, which means rebalance strategy was not executed 100% this cycle
     // but this will be corrected on the next rebalance cycle.
-    let reb_msg = if !msgs.is_empty() {
+    let mut reb_msgs: Vec<Binary> = vec![];
+    if !msgs.is_empty() {
         match config.account_type {
             valence_package::services::rebalancer::RebalancerAccountType::Regular => {
-                Some(to_json_binary(
-                    &valence_package::msgs::core_execute::AccountBaseExecuteMsg::SendFundsByService {
-                        msgs,
-                        atomic: false,
-                    },
-                )?)
-            },
+                // CW20 tokens are sent by executing the token contract without funds,
+                // so they can't be sent together with the native funds
+                let (cw20_msgs, funds_msgs): (Vec<CosmosMsg>, Vec<CosmosMsg>) =
+                    msgs.into_iter().partition(|msg| {
+                        matches!(msg, CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) if funds.is_empty())
+                    });
+
+                if !funds_msgs.is_empty() {
+                    reb_msgs.push(to_json_binary(
+                        &valence_package::msgs::core_execute::AccountBaseExecuteMsg::SendFundsByService {
+                            msgs: funds_msgs,
+                            atomic: false,
+                        },
+                    )?);
+                }
+                if !cw20_msgs.is_empty() {
+                    reb_msgs.push(to_json_binary(
+                        &valence_package::msgs::core_execute::AccountBaseExecuteMsg::ExecuteByService {
+                            msgs: cw20_msgs,
+                            atomic: false,
+                        },
+                    )?);
+                }
+            }
             valence_package::services::rebalancer::RebalancerAccountType::Program => {
-                let msgs = msgs.into_iter().map(|msg| {
-                    SubMsg::reply_on_error(msg, 0)
-                }).collect();
+                let msgs = msgs
+                    .into_iter()
+                    .map(|msg| SubMsg::reply_on_error(msg, 0))
+                    .collect();
 
-                Some(to_json_binary(
+                reb_msgs.push(to_json_binary(
                     &valence_package::services::rebalancer::MockProgramExecuteMsg::ExecuteSubmsgs {
                         msgs,
                         payload: None,
                     },
-                )?)
-            },
+                )?);
+            }
         }
-    } else {
-        None
-    };
+    }
 
-    let msg = reb_msg.map(|reb_msg| {
-        SubMsg::reply_on_error(
-            WasmMsg::Execute {
-                contract_addr: account.to_string(),
-                msg: reb_msg,
-                funds: vec![],
-            },
-            REPLY_DEFAULT_REBALANCE,
-        )
-    });
+    let msgs = reb_msgs
+        .into_iter()
+        .map(|reb_msg| {
+            SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: account.to_string(),
+                    msg: reb_msg,
+                    funds: vec![],
+                },
+                REPLY_DEFAULT_REBALANCE,
+            )
+        })
+        .collect();
 
     // We edit config to save data for the next rebalance calculation
     config.last_rebalance = env.block.time;
@@ -358,7 +381,7 @@ pub fn do_rebalance(
         trades,
     };
 
-    Ok(RebalanceResponse::new(config, msg, event, false))
+    Ok(RebalanceResponse::new(config, msgs, event, false))
 }
 
 /// Set the min amount an auction is willing to accept for a specific token
@@ -447,9 +470,21 @@ pub fn get_prices(
 fn get_inputs(
     deps: Deps,
     account: &Addr,
+    auction_manager: &Addr,
     config: &RebalancerConfig,
     prices: &[(Pair, Decimal)],
 ) -> Result<(Decimal, Vec<TargetHelper>), ContractError> {
+    let cw20_denoms = query_cw20_denoms(
+        deps,
+        auction_manager,
+        &config.base_denom,
+        &config
+            .targets
+            .iter()
+            .map(|target| target.denom.as_str())
+            .collect::<Vec<&str>>(),
+    )?;
+
     // get inputs per target (balance amount / price),
     // and current total input of the account (vec![denom / price].sum())
     config.targets.iter().try_fold(
@@ -470,7 +505,7 @@ fn get_inputs(
 
             // Get current balance of the target, and calculate the value
             // safe if balance is 0, 0 / price = 0
-            let current_balance = deps.querier.query_balance(account, target.denom.clone())?;
+            let current_balance = query_denom_balance(deps, account, &target.denom, &cw20_denoms)?;
             let balance_value =
                 Decimal::from_atomics(current_balance.amount, 0)?.checked_div(price)?;
 
@@ -633,14 +668,38 @@ fn construct_msg(
     auction_manager: Addr,
     trade: RebalanceTrade,
 ) -> Result<CosmosMsg, ContractError> {
-    let Some(pair_addr) = PAIRS.query(&deps.querier, auction_manager, trade.pair.clone())? else {
+    let Some(pair_addr) =
+        PAIRS.query(&deps.querier, auction_manager.clone(), trade.pair.clone())?
+    else {
         return Err(ContractError::PairDoesntExists(trade.pair.0, trade.pair.1));
     };
 
-    let msg = WasmMsg::Execute {
-        contract_addr: pair_addr.to_string(),
-        msg: to_json_binary(&auction::msg::ExecuteMsg::AuctionFunds {})?,
-        funds: coins(trade.amount.u128(), trade.pair.0),
+    // Deprecated auctions don't accept new funds
+    if DEPRECATED_PAIRS
+        .query(&deps.querier, auction_manager, trade.pair.clone())?
+        .is_some()
+    {
+        return Err(ContractError::PairIsDeprecated(trade.pair.0, trade.pair.1));
+    }
+
+    // CW20 tokens are sent to the auction with a cw20 `Send` message
+    let auction_config = AUCTION_CONFIG.query(&deps.querier, pair_addr.clone())?;
+    let msg = if auction_config.is_cw20(&trade.pair.0) {
+        WasmMsg::Execute {
+            contract_addr: trade.pair.0,
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: pair_addr.to_string(),
+                amount: trade.amount,
+                msg: to_json_binary(&auction::msg::Cw20HookMsg::AuctionFunds {})?,
+            })?,
+            funds: vec![],
+        }
+    } else {
+        WasmMsg::Execute {
+            contract_addr: pair_addr.to_string(),
+            msg: to_json_binary(&auction::msg::ExecuteMsg::AuctionFunds {})?,
+            funds: coins(trade.amount.u128(), trade.pair.0),
+        }
     };
 
     Ok(msg.into())
@@ -29,4 +29,7 @@ pub enum AuctionError {
 
     #[error("Auction strategy end price must be greater than 0 and lower than 10000")]
     InvalidAuctionStrategyEndPrice,
+
+    #[error("Protocol fee must be greater than 0 and lower or equal to 10000")]
+    InvalidProtocolFee,
 }
@@ -1,11 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Deps, DepsMut, Env, MessageInfo, Response, Timestamp};
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdResult, Timestamp, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 use cw_utils::Expiration;
 
 use crate::{
     error::AuctionError,
-    states::{AdminChange, ADMIN, ADMIN_CHANGE},
-    Pair, PriceFreshnessStrategy,
+    states::{AdminChange, MinAmount, ADMIN, ADMIN_CHANGE},
+    ActiveAuctionStatus, AuctionStrategy, Pair, PriceFreshnessStrategy, ProtocolFee,
 };
 
 pub fn verify_admin(deps: Deps, info: &MessageInfo) -> Result<(), AuctionError> {
@@ -21,6 +25,26 @@ pub struct GetPriceResponse {
     pub time: Timestamp,
 }
 
+#[cw_serde]
+pub struct ActiveAuctionInfo {
+    pub pair: Pair,
+    pub addr: Addr,
+    pub status: ActiveAuctionStatus,
+    /// The price of the auction on the current block
+    pub price: Decimal,
+    /// The available amount of pair.0
+    pub available_amount: Uint128,
+    pub end_block: u64,
+}
+
+#[cw_serde]
+pub struct ActiveAuctionsResponse {
+    pub auctions: Vec<ActiveAuctionInfo>,
+    /// The last pair scanned on this page, use it as `start_after` to get the next page.
+    /// None when there are no more pairs to scan.
+    pub last_scanned: Option<Pair>,
+}
+
 #[cw_serde]
 pub struct ChainHaltConfig {
     /// Time in seconds of how much of a halt we accept
@@ -38,12 +62,104 @@ impl Default for ChainHaltConfig {
     }
 }
 
+#[cw_serde]
+pub struct AuctionSchedule {
+    /// The amount of blocks the auction runs for
+    pub duration_blocks: u64,
+    /// The minimum amount of blocks between opening 2 auctions
+    pub interval_blocks: u64,
+    /// The minimum amount of pair.0 that must be sent to the next auction to open it
+    pub min_funds: Uint128,
+    /// Optional reserve price of the opened auctions
+    pub reserve_price: Option<Decimal>,
+}
+
+/// Named auction config that can be used to create auctions and update many auctions at once
+#[cw_serde]
+pub struct AuctionTemplate {
+    pub auction_strategy: AuctionStrategy,
+    pub chain_halt_config: ChainHaltConfig,
+    pub price_freshness_strategy: PriceFreshnessStrategy,
+    /// The minimum amount of pair.0 to set when creating an auction from the template,
+    /// if not set, the stored minimum amount is used.
+    /// The minimum amount is shared by all auctions selling the denom, so `ApplyTemplate` doesn't set it
+    pub min_amount: Option<MinAmount>,
+}
+
+#[cw_serde]
+pub struct MigrationProgress {
+    /// The code id the auctions are migrated to
+    pub code_id: u64,
+    /// The last pair that was migrated, the next batch should start after it
+    pub last_migrated: Option<Pair>,
+    /// The amount of auctions migrated to the code id so far
+    pub migrated: u64,
+    /// True once all the auctions were migrated
+    pub finished: bool,
+}
+
 #[cw_serde]
 pub struct AuctionConfig {
     pub is_paused: bool,
     pub pair: Pair,
     pub chain_halt_config: ChainHaltConfig,
     pub price_freshness_strategy: PriceFreshnessStrategy,
+    /// Fee taken from the resolved amount of pair.1 when the auction is finished
+    #[serde(default)]
+    pub protocol_fee: Option<ProtocolFee>,
+    /// Denoms of the pair that are CW20 tokens, the denom of a CW20 token is its contract address
+    #[serde(default)]
+    pub cw20_denoms: Vec<String>,
+}
+
+impl AuctionConfig {
+    pub fn is_cw20(&self, denom: &str) -> bool {
+        self.cw20_denoms
+            .iter()
+            .any(|cw20_denom| cw20_denom == denom)
+    }
+
+    /// Messages to send the funds to the recipient, native funds are sent in a single bank message,
+    /// CW20 tokens are sent with a `Transfer` message each
+    pub fn send_funds_msgs(&self, recipient: &Addr, funds: Vec<Coin>) -> StdResult<Vec<CosmosMsg>> {
+        let mut msgs: Vec<CosmosMsg> = vec![];
+        let mut native_funds: Vec<Coin> = vec![];
+
+        for fund in funds {
+            if fund.amount.is_zero() {
+                continue;
+            }
+
+            if self.is_cw20(&fund.denom) {
+                msgs.push(
+                    WasmMsg::Execute {
+                        contract_addr: fund.denom,
+                        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient: recipient.to_string(),
+                            amount: fund.amount,
+                        })?,
+                        funds: vec![],
+                    }
+                    .into(),
+                );
+            } else {
+                native_funds.push(fund);
+            }
+        }
+
+        if !native_funds.is_empty() {
+            msgs.insert(
+                0,
+                BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: native_funds,
+                }
+                .into(),
+            );
+        }
+
+        Ok(msgs)
+    }
 }
 
 pub fn start_admin_change(
@@ -1,7 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Timestamp};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
 
 pub mod error;
 pub mod helpers;
@@ -34,6 +34,54 @@ impl AuctionStrategy {
     }
 }
 
+/// Fee taken from the amount of pair.1 resolved in an auction, sent to the treasury
+#[cw_serde]
+pub struct ProtocolFee {
+    /// The fee in BPS of the resolved amount of pair.1
+    pub bps: u64,
+    /// The address the fee is sent to
+    pub treasury: Addr,
+}
+
+impl ProtocolFee {
+    pub fn verify(&self) -> Result<(), AuctionError> {
+        if self.bps == 0 || self.bps > 10000 {
+            return Err(AuctionError::InvalidProtocolFee);
+        }
+
+        Ok(())
+    }
+}
+
+/// Restricts who can bid on an auction
+#[cw_serde]
+pub enum BidderGate {
+    /// Only addresses on the allowlist of the auction can bid
+    Allowlist,
+    /// Only members of the cw4 group contract can bid
+    Cw4Group(Addr),
+}
+
+/// Where the dust leftovers of an auction are swept to
+#[cw_serde]
+pub enum DustDestination {
+    /// Send the dust to the recipient
+    Recipient(Addr),
+    /// Redistribute the dust to the funds providers of the latest auction
+    LatestProviders,
+}
+
+/// How auction funds are settled with the funds providers once the auction is finished
+#[cw_serde]
+#[derive(Default)]
+pub enum SettlementMode {
+    /// Funds are sent to all funds providers in batches when finishing the auction
+    #[default]
+    Push,
+    /// Funds providers (or anyone on their behalf) claim their share after the auction is finished
+    Claim,
+}
+
 /// Gives us the strategy we should use for when the data is not fresh.
 /// "multiplier" list is sorted in descending order, so after we check the list,
 /// if the data is fresh, the multiplier is 1.
@@ -61,6 +109,20 @@ impl Default for PriceFreshnessStrategy {
     }
 }
 
+#[cw_serde]
+pub enum ActiveAuctionStatus {
+    /// The auction started, and last resolved block height is (u64)
+    Started,
+    /// The auction is finished, waiting for the funds to be resolved
+    Finished,
+    /// Handle closing auction, addr of the last funds provider we resolved
+    /// and the total amounts of the pair we sent already
+    /// (provider, total_amount_pair.0, total_amount_pair.1)
+    CloseAuction(Option<Addr>, Uint128, Uint128),
+    /// The auction is closed
+    AuctionClosed,
+}
+
 #[cw_serde]
 pub struct Price {
     pub price: Decimal,
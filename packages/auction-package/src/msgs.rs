@@ -1,8 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin};
 
 use crate::{
-    helpers::{AuctionConfig, GetPriceResponse},
+    helpers::{
+        ActiveAuctionsResponse, AuctionConfig, AuctionSchedule, AuctionTemplate, GetPriceResponse,
+        MigrationProgress,
+    },
     states::MinAmount,
     Pair,
 };
@@ -17,6 +20,14 @@ pub enum AuctionsManagerQueryMsg {
         limit: Option<u32>,
     },
 
+    /// Get the status, current price and available amount of all auctions
+    /// that are opened and not closed yet, `limit` is the number of pairs scanned
+    #[returns(ActiveAuctionsResponse)]
+    GetAllActiveAuctions {
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    },
+
     /// Get the price of a specific pair
     #[returns(GetPriceResponse)]
     GetPrice { pair: Pair },
@@ -41,4 +52,27 @@ pub enum AuctionsManagerQueryMsg {
 
     #[returns(Addr)]
     GetServerAddr,
+
+    /// Get the schedule of the auction of the pair
+    #[returns(AuctionSchedule)]
+    GetAuctionSchedule { pair: Pair },
+
+    /// Get the tip paid for every auction opened by `OpenDueAuctions`
+    #[returns(Option<Coin>)]
+    GetKeeperTip,
+
+    /// Get the progress of the last `MigrateAllAuctions`
+    #[returns(Option<MigrationProgress>)]
+    GetMigrationProgress,
+
+    /// Get the auction template by its name
+    #[returns(AuctionTemplate)]
+    GetTemplate { name: String },
+
+    /// Get all auction templates
+    #[returns(Vec<(String, AuctionTemplate)>)]
+    GetTemplates {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
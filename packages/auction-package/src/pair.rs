@@ -1,7 +1,7 @@
 use std::fmt::{self, Display};
 
 use cosmwasm_std::{from_json, StdError, StdResult};
-use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+use cw_storage_plus::{KeyDeserialize, Prefixer, PrimaryKey};
 use serde::{
     de,
     ser::{self, SerializeSeq},
@@ -31,13 +31,19 @@ impl<'a> PrimaryKey<'a> for Pair {
     type Suffix = <String as PrimaryKey<'a>>::Suffix;
     type SuperSuffix = <String as PrimaryKey<'a>>::SuperSuffix;
 
-    fn key(&self) -> Vec<cw_storage_plus::Key> {
+    fn key(&self) -> Vec<cw_storage_plus::Key<'_>> {
         let mut a = self.0.key();
         a.extend(self.1.key());
         a
     }
 }
 
+impl<'a> Prefixer<'a> for Pair {
+    fn prefix(&self) -> Vec<cw_storage_plus::Key<'_>> {
+        self.key()
+    }
+}
+
 impl Pair {
     pub fn verify(&self) -> Result<(), AuctionError> {
         if self.0.is_empty() || self.1.is_empty() || self.0 == self.1 {
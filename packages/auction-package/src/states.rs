@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
 
@@ -15,6 +15,8 @@ pub const ORACLE_ADDR: Item<Addr> = Item::new("oracle_addr");
 pub const PRICES: Map<Pair, Price> = Map::new("prices");
 /// Map from Pair to auction contract address
 pub const PAIRS: Map<Pair, Addr> = Map::new("pairs");
+/// Pairs that are deprecated on the auctions manager, and don't accept new funds
+pub const DEPRECATED_PAIRS: Map<Pair, Empty> = Map::new("deprecated_pairs");
 
 /// TWAP prices of the auction
 pub const TWAP_PRICES: Item<VecDeque<Price>> = Item::new("twap_prices");
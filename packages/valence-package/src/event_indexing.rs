@@ -1,9 +1,10 @@
 use std::{collections::HashSet, fmt};
 
 use auction_package::{
-    helpers::{AuctionConfig, ChainHaltConfig},
+    helpers::{AuctionConfig, AuctionSchedule, AuctionTemplate, ChainHaltConfig},
     states::MinAmount,
-    AuctionStrategy, Pair, PriceFreshnessStrategy,
+    AuctionStrategy, BidderGate, DustDestination, Pair, PriceFreshnessStrategy, ProtocolFee,
+    SettlementMode,
 };
 use cosmwasm_std::{
     to_json_binary, Binary, Coin, CosmosMsg, Decimal, Empty, Event, SubMsg, Uint128,
@@ -78,6 +79,10 @@ where
         price: Decimal,
         source: String,
     },
+    OracleUpdatePriceFailed {
+        pair: Pair,
+        error: String,
+    },
     OracleAddPath {
         pair: Pair,
         path: Vec<E>,
@@ -86,9 +91,41 @@ where
         pair: Pair,
         path: Vec<E>,
     },
+    OracleSetPriceSources {
+        pair: Pair,
+        sources: E,
+    },
+    OracleRemovePriceSources {
+        pair: Pair,
+    },
+    OracleHoldPendingPrice {
+        pair: Pair,
+        price: Decimal,
+        source: String,
+    },
+    OracleApprovePendingPrice {
+        pair: Pair,
+        price: Decimal,
+    },
+    OracleRejectPendingPrice {
+        pair: Pair,
+    },
+    OracleAddRole {
+        role: String,
+        addr: String,
+        pairs: Option<Vec<Pair>>,
+    },
+    OracleRemoveRole {
+        role: String,
+        addr: String,
+    },
     OracleUpdateConfig {
         config: E,
     },
+    OracleUpdateTwapConfig {
+        pair: Pair,
+        config: E,
+    },
     OracleStartAdminChange {
         admin: String,
     },
@@ -107,6 +144,11 @@ where
         code_id: u64,
         msg: E,
     },
+    AuctionManagerMigrateAllAuctions {
+        pairs: Vec<Pair>,
+        code_id: u64,
+        msg: E,
+    },
     AuctionManagerUpdateMinAmount {
         denom: String,
         min_amount: MinAmount,
@@ -119,6 +161,54 @@ where
     },
     AuctionManagerCancelAdminChange {},
     AuctionManagerApproveAdminChange {},
+    AuctionManagerSetAuctionSchedule {
+        pair: Pair,
+        schedule: AuctionSchedule,
+    },
+    AuctionManagerRemoveAuctionSchedule {
+        pair: Pair,
+    },
+    AuctionManagerUpdateKeeperTip {
+        tip: Option<Coin>,
+    },
+    AuctionManagerOpenDueAuctions {
+        pairs: Vec<Pair>,
+    },
+    AuctionManagerOpenScheduledAuction {
+        pair: Pair,
+        tip: Option<Coin>,
+    },
+    AuctionManagerStepFailed {
+        pair: Pair,
+        step: String,
+        error: String,
+    },
+    AuctionManagerDeprecateAuction {
+        pair: Pair,
+    },
+    AuctionManagerRefundDeprecatedLeftovers {
+        pair: Pair,
+    },
+    AuctionManagerSetTemplate {
+        name: String,
+        template: AuctionTemplate,
+    },
+    AuctionManagerRemoveTemplate {
+        name: String,
+    },
+    AuctionManagerApplyTemplate {
+        name: String,
+        pairs: Vec<Pair>,
+    },
+    AuctionManagerRemoveAuction {
+        pair: Pair,
+    },
+    AuctionManagerCrank {
+        finished: Vec<Pair>,
+        swept: Vec<Pair>,
+        cleaned: Vec<Pair>,
+        opened: Vec<Pair>,
+    },
 
     // Auctions
     AuctionInit {
@@ -146,6 +236,7 @@ where
     },
     AuctionPause {},
     AuctionResume {},
+    AuctionDeprecate {},
     AuctionUpdateStrategy {
         strategy: AuctionStrategy,
     },
@@ -155,6 +246,47 @@ where
     AuctionUpdatePriceFreshnessStrategy {
         strategy: PriceFreshnessStrategy,
     },
+    AuctionUpdateSettlementMode {
+        mode: SettlementMode,
+    },
+    AuctionUpdateProtocolFee {
+        fee: Option<ProtocolFee>,
+    },
+    AuctionUpdateReclaimThreshold {
+        seconds: u64,
+    },
+    AuctionReclaimFunds {
+        auction_id: u64,
+        address: String,
+        /// How much of token.0 wasn't sold and was sent back to the provider
+        unsold_amount: Uint128,
+        /// How much of token.1 the provider received from what was already sold
+        bought_amount: Uint128,
+    },
+    AuctionUpdateDustDestination {
+        destination: Option<DustDestination>,
+    },
+    AuctionSweepDust {
+        auction_id: u64,
+        dust: Vec<Coin>,
+        destination: DustDestination,
+    },
+    AuctionRefundLeftovers {
+        auction_id: u64,
+        leftovers: Vec<Coin>,
+    },
+    AuctionUpdateBidderGate {
+        gate: Option<BidderGate>,
+    },
+    AuctionUpdateBidderAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    AuctionProtocolFee {
+        auction_id: u64,
+        fee: Coin,
+        treasury: String,
+    },
     AuctionOpen {
         auction_id: u64,
         auction: E,
@@ -171,6 +303,14 @@ where
         price: String,
         accounts: u64,
     },
+    AuctionClaim {
+        auction_id: u64,
+        address: String,
+        /// How much of token.1 the provider received
+        bought_amount: Uint128,
+        /// How much of token.0 wasn't sold and was sent back to the provider
+        unsold_amount: Uint128,
+    },
 
     // Services manager
     ServicesManagerAddService {
@@ -293,9 +433,32 @@ impl<E: serde::Serialize> fmt::Display for ValenceGenericEvent<E> {
 
             // oracle
             ValenceGenericEvent::OracleUpdatePrice { .. } => write!(f, "oracle-update-price"),
+            ValenceGenericEvent::OracleUpdatePriceFailed { .. } => {
+                write!(f, "oracle-update-price-failed")
+            }
             ValenceGenericEvent::OracleAddPath { .. } => write!(f, "oracle-add-path"),
             ValenceGenericEvent::OracleUpdatePath { .. } => write!(f, "oracle-update-path"),
+            ValenceGenericEvent::OracleSetPriceSources { .. } => {
+                write!(f, "oracle-set-price-sources")
+            }
+            ValenceGenericEvent::OracleRemovePriceSources { .. } => {
+                write!(f, "oracle-remove-price-sources")
+            }
+            ValenceGenericEvent::OracleHoldPendingPrice { .. } => {
+                write!(f, "oracle-hold-pending-price")
+            }
+            ValenceGenericEvent::OracleApprovePendingPrice { .. } => {
+                write!(f, "oracle-approve-pending-price")
+            }
+            ValenceGenericEvent::OracleRejectPendingPrice { .. } => {
+                write!(f, "oracle-reject-pending-price")
+            }
+            ValenceGenericEvent::OracleAddRole { .. } => write!(f, "oracle-add-role"),
+            ValenceGenericEvent::OracleRemoveRole { .. } => write!(f, "oracle-remove-role"),
             ValenceGenericEvent::OracleUpdateConfig { .. } => write!(f, "oracle-update-config"),
+            ValenceGenericEvent::OracleUpdateTwapConfig { .. } => {
+                write!(f, "oracle-update-twap-config")
+            }
             ValenceGenericEvent::OracleStartAdminChange { .. } => {
                 write!(f, "oracle-start-admin-change")
             }
@@ -316,6 +479,9 @@ impl<E: serde::Serialize> fmt::Display for ValenceGenericEvent<E> {
             ValenceGenericEvent::AuctionManagerMigrateAuction { .. } => {
                 write!(f, "auction-manager-migrate-auction")
             }
+            ValenceGenericEvent::AuctionManagerMigrateAllAuctions { .. } => {
+                write!(f, "auction-manager-migrate-all-auctions")
+            }
             ValenceGenericEvent::AuctionManagerUpdateMinAmount { .. } => {
                 write!(f, "auction-manager-update-min-amount")
             }
@@ -331,6 +497,43 @@ impl<E: serde::Serialize> fmt::Display for ValenceGenericEvent<E> {
             ValenceGenericEvent::AuctionManagerChangeServerAddr { .. } => {
                 write!(f, "auction-manager-change-server-addr")
             }
+            ValenceGenericEvent::AuctionManagerSetAuctionSchedule { .. } => {
+                write!(f, "auction-manager-set-auction-schedule")
+            }
+            ValenceGenericEvent::AuctionManagerRemoveAuctionSchedule { .. } => {
+                write!(f, "auction-manager-remove-auction-schedule")
+            }
+            ValenceGenericEvent::AuctionManagerUpdateKeeperTip { .. } => {
+                write!(f, "auction-manager-update-keeper-tip")
+            }
+            ValenceGenericEvent::AuctionManagerOpenDueAuctions { .. } => {
+                write!(f, "auction-manager-open-due-auctions")
+            }
+            ValenceGenericEvent::AuctionManagerOpenScheduledAuction { .. } => {
+                write!(f, "auction-manager-open-scheduled-auction")
+            }
+            ValenceGenericEvent::AuctionManagerStepFailed { .. } => {
+                write!(f, "auction-manager-step-failed")
+            }
+            ValenceGenericEvent::AuctionManagerCrank { .. } => write!(f, "auction-manager-crank"),
+            ValenceGenericEvent::AuctionManagerDeprecateAuction { .. } => {
+                write!(f, "auction-manager-deprecate-auction")
+            }
+            ValenceGenericEvent::AuctionManagerRefundDeprecatedLeftovers { .. } => {
+                write!(f, "auction-manager-refund-deprecated-leftovers")
+            }
+            ValenceGenericEvent::AuctionManagerSetTemplate { .. } => {
+                write!(f, "auction-manager-set-template")
+            }
+            ValenceGenericEvent::AuctionManagerRemoveTemplate { .. } => {
+                write!(f, "auction-manager-remove-template")
+            }
+            ValenceGenericEvent::AuctionManagerApplyTemplate { .. } => {
+                write!(f, "auction-manager-apply-template")
+            }
+            ValenceGenericEvent::AuctionManagerRemoveAuction { .. } => {
+                write!(f, "auction-manager-remove-auction")
+            }
 
             // auctions
             ValenceGenericEvent::AuctionInit { .. } => write!(f, "auction-init"),
@@ -339,6 +542,7 @@ impl<E: serde::Serialize> fmt::Display for ValenceGenericEvent<E> {
             ValenceGenericEvent::AuctionDoBid { .. } => write!(f, "auction-do-bid"),
             ValenceGenericEvent::AuctionPause {} => write!(f, "auction-pause"),
             ValenceGenericEvent::AuctionResume {} => write!(f, "auction-resume"),
+            ValenceGenericEvent::AuctionDeprecate {} => write!(f, "auction-deprecate"),
             ValenceGenericEvent::AuctionUpdateStrategy { .. } => {
                 write!(f, "auction-update-strategy")
             }
@@ -348,9 +552,34 @@ impl<E: serde::Serialize> fmt::Display for ValenceGenericEvent<E> {
             ValenceGenericEvent::AuctionUpdatePriceFreshnessStrategy { .. } => {
                 write!(f, "auction-update-price-freshness-strategy")
             }
+            ValenceGenericEvent::AuctionUpdateSettlementMode { .. } => {
+                write!(f, "auction-update-settlement-mode")
+            }
+            ValenceGenericEvent::AuctionUpdateProtocolFee { .. } => {
+                write!(f, "auction-update-protocol-fee")
+            }
+            ValenceGenericEvent::AuctionProtocolFee { .. } => write!(f, "auction-protocol-fee"),
+            ValenceGenericEvent::AuctionUpdateReclaimThreshold { .. } => {
+                write!(f, "auction-update-reclaim-threshold")
+            }
+            ValenceGenericEvent::AuctionReclaimFunds { .. } => write!(f, "auction-reclaim-funds"),
+            ValenceGenericEvent::AuctionUpdateDustDestination { .. } => {
+                write!(f, "auction-update-dust-destination")
+            }
+            ValenceGenericEvent::AuctionSweepDust { .. } => write!(f, "auction-sweep-dust"),
+            ValenceGenericEvent::AuctionRefundLeftovers { .. } => {
+                write!(f, "auction-refund-leftovers")
+            }
+            ValenceGenericEvent::AuctionUpdateBidderGate { .. } => {
+                write!(f, "auction-update-bidder-gate")
+            }
+            ValenceGenericEvent::AuctionUpdateBidderAllowlist { .. } => {
+                write!(f, "auction-update-bidder-allowlist")
+            }
             ValenceGenericEvent::AuctionOpen { .. } => write!(f, "auction-open"),
             ValenceGenericEvent::AuctionOpenRefund { .. } => write!(f, "auction-open-refund"),
             ValenceGenericEvent::AuctionClose { .. } => write!(f, "auction-close"),
+            ValenceGenericEvent::AuctionClaim { .. } => write!(f, "auction-claim"),
 
             // Services manager
             ValenceGenericEvent::ServicesManagerAddService { .. } => {
@@ -34,6 +34,8 @@ LABEL="auction TEST/NTRN"
 AUCTION_STRATEGY='{ "start_price_perc": 5000, "end_price_perc": 5000 }'
 CHAIN_HALT='{ "cap": "14400", "block_avg": "3" }'
 PRICE_FRESHNESS='{ "limit": "3", "multipliers": [["2", "2"], ["1", "1.5"]] }'
+# Name of a template registered in the auctions manager, if set, the config above is ignored
+TEMPLATE=""
 
 while [[ "$#" -gt 0 ]]; do
   case $1 in
@@ -53,6 +55,10 @@ while [[ "$#" -gt 0 ]]; do
     PRICE_FRESHNESS="$2"
     shift
     ;;
+  -t | --template)
+    TEMPLATE="$2"
+    shift
+    ;;
   *)
     echo "Unknown parameter passed: $1"
     exit 1
@@ -63,10 +69,11 @@ done
 
 execute_msg=$(jq -n \
   --argjson pair "$PAIR" \
-  --arg label "$LABEL" \
+  --arg auction_label "$LABEL" \
   --argjson auction_strategy "$AUCTION_STRATEGY" \
   --argjson chain_halt_config "$CHAIN_HALT" \
   --argjson price_freshness_strategy "$PRICE_FRESHNESS" \
+  --arg template "$TEMPLATE" \
   '{admin: {
       new_auction: {
         msg: {
@@ -75,7 +82,8 @@ execute_msg=$(jq -n \
           chain_halt_config: $chain_halt_config,
           price_freshness_strategy: $price_freshness_strategy
         },
-        label: "$label",
+        label: $auction_label,
+        template: (if $template == "" then null else $template end)
       }
     }}')
 
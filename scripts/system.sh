@@ -152,6 +152,25 @@ elif [[ "$COMMAND" == 'close-auctions' ]]; then
     $BINARY tx wasm execute $AUCTIONS_MANAGER "$execute_msg" --from $OWNER_ADDR $EXECUTE_FLAGS
   done
 
+elif [[ "$COMMAND" == 'crank' ]]; then
+  LIMIT=$1
+  shift
+
+  if [ -z "$LIMIT" ]; then
+    execute_msg=$(jq -n \
+      '{crank: {}}')
+
+  else
+    execute_msg=$(jq -n \
+      --argjson limit $LIMIT \
+      '{crank: {
+      limit: $limit,
+    }}')
+
+  fi
+
+  $BINARY tx wasm execute $AUCTIONS_MANAGER "$execute_msg" --from $OWNER_ADDR $EXECUTE_FLAGS
+
 elif [[ "$COMMAND" == 'do-bid' ]]; then
   AMOUNT=$1
   shift
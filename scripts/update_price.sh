@@ -39,7 +39,12 @@ EXECUTE_FLAGS="--gas-prices $GAS_PRICES --gas auto --gas-adjustment 1.4 --output
 
 if [ -z "$ORACLE_ADDR" ]; then echo "[ERROR] Oracle address is missing for $CHAIN" && exit 1; fi
 
-if [ -z "$PRICE" ]; then
+if [ -z "$PAIR1" ]; then
+  # Update the prices of all pairs in batches, call again until all pairs are updated
+  execute_msg=$(jq -n \
+    '{update_prices: {}}')
+
+elif [ -z "$PRICE" ]; then
   execute_msg=$(jq -n \
     --arg pair1 "$PAIR1" \
     --arg pair2 "$PAIR2" \
@@ -90,6 +90,7 @@ impl SuiteBuilder {
                             msg: auction_init_msg.into(),
                             label: "auction".to_string(),
                             min_amount: Some(min_amount),
+                            template: None,
                         },
                     )),
                     &[],
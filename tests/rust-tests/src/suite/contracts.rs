@@ -37,7 +37,8 @@ pub fn auction_contract() -> Box<dyn Contract<Empty>> {
         auction::contract::execute,
         auction::contract::instantiate,
         auction::contract::query,
-    );
+    )
+    .with_migrate(auction::contract::migrate);
     Box::new(contract)
 }
 
@@ -60,6 +61,33 @@ pub fn oracle_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn mock_price_source_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        super::mock_price_source::execute,
+        super::mock_price_source::instantiate,
+        super::mock_price_source::query,
+    );
+    Box::new(contract)
+}
+
+pub fn mock_cw4_group_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        super::mock_cw4_group::execute,
+        super::mock_cw4_group::instantiate,
+        super::mock_cw4_group::query,
+    );
+    Box::new(contract)
+}
+
+pub fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
 pub fn astro_token_contract() -> Box<dyn Contract<Empty>> {
     Box::new(
         ContractWrapper::new(
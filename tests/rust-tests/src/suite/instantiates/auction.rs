@@ -97,6 +97,7 @@ impl AuctionInstantiate {
                         (Decimal::bps(10000), Decimal::bps(15000)),
                     ],
                 },
+                cw20_denoms: vec![],
             },
         }
     }
@@ -107,6 +108,11 @@ impl AuctionInstantiate {
         self
     }
 
+    pub fn change_cw20_denoms(&mut self, cw20_denoms: Vec<String>) -> &mut Self {
+        self.msg.cw20_denoms = cw20_denoms;
+        self
+    }
+
     pub fn change_auction_strategy(&mut self, auction_strategy: AuctionStrategy) -> &mut Self {
         self.msg.auction_strategy = auction_strategy;
         self
@@ -0,0 +1,66 @@
+//! Stand-in for a cw4 group contract, keeps the members in the same raw storage as cw4-group,
+//! so `cw4::Cw4Contract::is_member` raw queries work against it.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use cw_storage_plus::Map;
+
+const MEMBERS: Map<&Addr, u64> = Map::new(cw4::MEMBERS_KEY);
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub members: Vec<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateMembers {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    Member { addr: String },
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    for member in msg.members {
+        MEMBERS.save(deps.storage, &deps.api.addr_validate(&member)?, &1)?;
+    }
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateMembers { add, remove } => {
+            for member in add {
+                MEMBERS.save(deps.storage, &deps.api.addr_validate(&member)?, &1)?;
+            }
+            for member in remove {
+                MEMBERS.remove(deps.storage, &deps.api.addr_validate(&member)?);
+            }
+        }
+    };
+    Ok(Response::default())
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Member { addr } => {
+            to_json_binary(&MEMBERS.may_load(deps.storage, &deps.api.addr_validate(&addr)?)?)
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Stand-in contract for the oracle price sources that don't exist in cw-multi-test,
+//! answers the osmosis pool, pair simulation and price feed queries with a fixed price.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, Int64, MessageInfo, Response, StdResult,
+    Uint128, Uint64,
+};
+use cw_storage_plus::Item;
+use price_oracle::sources::{
+    FeedPrice, PriceFeed, PriceFeedResponse, SimulationResponse, SpotPriceResponse,
+};
+
+/// Price feeds return the price with 8 decimals
+const FEED_EXPO: i32 = -8;
+
+const PRICE: Item<Decimal> = Item::new("price");
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub price: Decimal,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    SetPrice { price: Decimal },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    SpotPrice {
+        quote_asset_denom: String,
+        base_asset_denom: String,
+    },
+    Simulation {
+        offer_asset: astroport::asset::Asset,
+    },
+    PriceFeed {
+        id: String,
+    },
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    PRICE.save(deps.storage, &msg.price)?;
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::SetPrice { price } => PRICE.save(deps.storage, &price)?,
+    };
+    Ok(Response::default())
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let price = PRICE.load(deps.storage)?;
+
+    match msg {
+        QueryMsg::SpotPrice { .. } => to_json_binary(&SpotPriceResponse { spot_price: price }),
+        QueryMsg::Simulation { offer_asset } => to_json_binary(&SimulationResponse {
+            return_amount: offer_asset.amount * price,
+            spread_amount: Uint128::zero(),
+            commission_amount: Uint128::zero(),
+        }),
+        QueryMsg::PriceFeed { id } => {
+            let feed_price = FeedPrice {
+                price: Int64::new(
+                    (Uint128::new(10_u128.pow(FEED_EXPO.unsigned_abs())) * price).u128() as i64,
+                ),
+                conf: Uint64::zero(),
+                expo: FEED_EXPO,
+                publish_time: env.block.time.seconds() as i64,
+            };
+
+            to_json_binary(&PriceFeedResponse {
+                price_feed: PriceFeed {
+                    id,
+                    price: feed_price.clone(),
+                    ema_price: feed_price,
+                },
+            })
+        }
+    }
+}
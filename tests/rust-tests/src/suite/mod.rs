@@ -1,6 +1,8 @@
 pub mod builder_astro;
 pub mod contracts;
 pub mod instantiates;
+pub mod mock_cw4_group;
+pub mod mock_price_source;
 #[allow(clippy::module_inception)]
 pub mod suite;
 pub mod suite_auction;
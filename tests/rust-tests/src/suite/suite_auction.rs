@@ -1,19 +1,35 @@
 use auction::{
-    msg::{GetFundsAmountResponse, NewAuctionParams},
-    state::ActiveAuction,
+    msg::{GetAuctionFillsResponse, GetFundsAmountResponse, NewAuctionParams},
+    state::{ActiveAuction, DustProvidersTotal, DustSweep, ProviderReceipt, DUST_PROVIDERS_TOTAL},
 };
 use auction_package::{
-    helpers::{ChainHaltConfig, GetPriceResponse},
+    helpers::{
+        ActiveAuctionsResponse, AuctionSchedule, AuctionTemplate, ChainHaltConfig,
+        GetPriceResponse, MigrationProgress,
+    },
     msgs::AuctionsManagerQueryMsg,
     states::MinAmount,
-    AuctionStrategy, Pair, Price, PriceFreshnessStrategy,
+    AuctionStrategy, BidderGate, DustDestination, Pair, Price, PriceFreshnessStrategy, ProtocolFee,
+    SettlementMode,
 };
-use cosmwasm_std::{coin, coins, Addr, Coin, Decimal, SignedDecimal, Uint128};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Coin, Decimal, SignedDecimal, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
 use cw_multi_test::{AppResponse, Executor};
-use price_oracle::state::PriceStep;
+use price_oracle::{
+    msg::{HealthResponse, PriceDetailsResponse, StalePricesResponse},
+    sources::PriceSources,
+    state::{PendingPrice, PriceHistoryEntry, PriceStep, Role, RoleScope, TwapConfig},
+};
 use rand::{rngs::ThreadRng, Rng};
 
-use super::suite::{Suite, ATOM, DAY, DEFAULT_BLOCK_TIME, HALF_DAY, NTRN};
+use super::{
+    contracts::{
+        cw20_contract, mock_cw4_group_contract, mock_price_source_contract, oracle_contract,
+    },
+    instantiates::{AuctionInstantiate, OracleInstantiate},
+    mock_cw4_group, mock_price_source,
+    suite::{Suite, ATOM, DAY, DEFAULT_BLOCK_TIME, HALF_DAY, NTRN},
+};
 
 // Executables
 impl Suite {
@@ -32,6 +48,7 @@ impl Suite {
                         msg: init_msg.clone(),
                         label: "label".to_string(),
                         min_amount,
+                        template: None,
                     },
                 )),
                 &[],
@@ -67,6 +84,7 @@ impl Suite {
                         msg: init_msg,
                         label: "label".to_string(),
                         min_amount,
+                        template: None,
                     },
                 )),
                 &[],
@@ -110,18 +128,27 @@ impl Suite {
         pair: Pair,
         start_block: Option<u64>,
         end_block: u64,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.start_auction_with_params(
+            pair,
+            NewAuctionParams {
+                start_block,
+                end_block,
+                reserve_price: None,
+            },
+        )
+    }
+
+    pub fn start_auction_with_params(
+        &mut self,
+        pair: Pair,
+        params: NewAuctionParams,
     ) -> Result<AppResponse, anyhow::Error> {
         self.app.execute_contract(
             self.mm.clone(),
             self.auctions_manager_addr.clone(),
             &auctions_manager::msg::ExecuteMsg::Server(
-                auctions_manager::msg::ServerMsgs::OpenAuction {
-                    pair,
-                    params: NewAuctionParams {
-                        start_block,
-                        end_block,
-                    },
-                },
+                auctions_manager::msg::ServerMsgs::OpenAuction { pair, params },
             ),
             &[],
         )
@@ -164,6 +191,27 @@ impl Suite {
         )
     }
 
+    /// Send CW20 tokens to the auctions manager to be auctioned on the auction of the pair
+    pub fn auction_funds_cw20(
+        &mut self,
+        user: Addr,
+        token: Addr,
+        pair: Pair,
+        amount: u128,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            user,
+            token,
+            &Cw20ExecuteMsg::Send {
+                contract: self.auctions_manager_addr.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&auctions_manager::msg::Cw20HookMsg::AuctionFunds { pair })
+                    .unwrap(),
+            },
+            &[],
+        )
+    }
+
     pub fn do_bid_err(&mut self, pair: Pair, amount: Coin) -> auction::error::ContractError {
         let auction_addr = self
             .app
@@ -323,27 +371,102 @@ impl Suite {
 
     // Permissionless price udpate method
     pub fn update_price(&mut self, pair: Pair) -> Result<AppResponse, anyhow::Error> {
+        self.update_price_from(self.admin.clone(), pair)
+    }
+
+    pub fn update_price_from(
+        &mut self,
+        sender: Addr,
+        pair: Pair,
+    ) -> Result<AppResponse, anyhow::Error> {
         self.app.execute_contract(
-            self.admin.clone(),
+            sender,
             self.oracle_addr.clone(),
             &price_oracle::msg::ExecuteMsg::UpdatePrice { pair },
             &[],
         )
     }
 
+    pub fn update_price_from_err(
+        &mut self,
+        sender: Addr,
+        pair: Pair,
+    ) -> price_oracle::error::ContractError {
+        self.update_price_from(sender, pair)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
+    pub fn update_prices(
+        &mut self,
+        pairs: Option<Vec<Pair>>,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::UpdatePrices { pairs, limit },
+            &[],
+        )
+    }
+
     pub fn manual_update_price(
         &mut self,
         pair: Pair,
         price: Decimal,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.manual_update_price_from(self.admin.clone(), pair, price)
+    }
+
+    pub fn manual_update_price_from(
+        &mut self,
+        sender: Addr,
+        pair: Pair,
+        price: Decimal,
     ) -> Result<AppResponse, anyhow::Error> {
         self.app.execute_contract(
-            self.admin.clone(),
+            sender,
             self.oracle_addr.clone(),
             &price_oracle::msg::ExecuteMsg::ManualPriceUpdate { pair, price },
             &[],
         )
     }
 
+    pub fn add_oracle_role(
+        &mut self,
+        role: Role,
+        addr: Addr,
+        pairs: Option<Vec<Pair>>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::AddRole {
+                role,
+                addr: addr.to_string(),
+                pairs,
+            },
+            &[],
+        )
+    }
+
+    pub fn remove_oracle_role(
+        &mut self,
+        role: Role,
+        addr: Addr,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::RemoveRole {
+                role,
+                addr: addr.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn update_price_err(&mut self, pair: Pair) -> price_oracle::error::ContractError {
         self.update_price(pair).unwrap_err().downcast().unwrap()
     }
@@ -511,6 +634,100 @@ impl Suite {
         self
     }
 
+    pub fn update_settlement_mode(&mut self, pair: Pair, mode: SettlementMode) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::UpdateSettlementMode { pair, mode },
+                )),
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    pub fn update_protocol_fee(
+        &mut self,
+        pair: Pair,
+        fee: Option<ProtocolFee>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::UpdateProtocolFee { pair, fee },
+            )),
+            &[],
+        )
+    }
+
+    pub fn update_bidder_gate(&mut self, pair: Pair, gate: Option<BidderGate>) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::UpdateBidderGate { pair, gate },
+                )),
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    pub fn update_bidder_allowlist(
+        &mut self,
+        pair: Pair,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::UpdateBidderAllowlist { pair, add, remove },
+                )),
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    pub fn claim(
+        &mut self,
+        user: Addr,
+        auction_addr: Addr,
+        auction_id: u64,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            user,
+            auction_addr,
+            &auction::msg::ExecuteMsg::Claim {
+                auction_id,
+                addr: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_err(
+        &mut self,
+        user: Addr,
+        auction_addr: Addr,
+        auction_id: u64,
+    ) -> auction::error::ContractError {
+        self.claim(user, auction_addr, auction_id)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
     pub fn withdraw_funds(
         &mut self,
         user: Addr,
@@ -548,6 +765,65 @@ impl Suite {
         self
     }
 
+    pub fn reclaim_funds(&mut self, pair: Pair, user: Addr) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            user,
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::ReclaimFunds { pair },
+            &[],
+        )
+    }
+
+    pub fn reclaim_funds_err(&mut self, pair: Pair, user: Addr) -> auction::error::ContractError {
+        self.reclaim_funds(pair, user)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
+    pub fn update_dust_destination(
+        &mut self,
+        pair: Pair,
+        destination: Option<DustDestination>,
+    ) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::UpdateDustDestination { pair, destination },
+                )),
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    pub fn sweep_dust(&mut self, auction_addr: Addr) -> Result<AppResponse, anyhow::Error> {
+        self.sweep_dust_with_limit(auction_addr, None)
+    }
+
+    pub fn sweep_dust_with_limit(
+        &mut self,
+        auction_addr: Addr,
+        limit: Option<u64>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            auction_addr,
+            &auction::msg::ExecuteMsg::SweepDust { limit },
+            &[],
+        )
+    }
+
+    pub fn sweep_dust_err(&mut self, auction_addr: Addr) -> auction::error::ContractError {
+        self.sweep_dust(auction_addr)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
     pub fn add_astro_path_to_oracle(
         &mut self,
         pair: Pair,
@@ -572,44 +848,258 @@ impl Suite {
             .unwrap()
     }
 
-    pub fn astro_swap(&mut self, pool_addr: Addr, coin: Coin) -> &mut Self {
-        let offer_asset = astroport::asset::Asset {
-            info: astroport::asset::AssetInfo::NativeToken {
-                denom: coin.denom.clone(),
-            },
-            amount: coin.amount,
-        };
+    pub fn update_astro_path_in_oracle(
+        &mut self,
+        pair: Pair,
+        path: Vec<PriceStep>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::UpdateAstroPath { pair, path },
+            &[],
+        )
+    }
 
-        let swap_msg = astroport::pair::ExecuteMsg::Swap {
-            offer_asset,
-            ask_asset_info: None,
-            belief_price: None,
-            max_spread: Some(Decimal::bps(5000)),
-            to: None,
-        };
+    pub fn set_oracle_price_sources(
+        &mut self,
+        pair: Pair,
+        sources: PriceSources,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::SetPriceSources { pair, sources },
+            &[],
+        )
+    }
+
+    pub fn set_oracle_price_sources_err(
+        &mut self,
+        pair: Pair,
+        sources: PriceSources,
+    ) -> price_oracle::error::ContractError {
+        self.set_oracle_price_sources(pair, sources)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
 
+    pub fn remove_oracle_price_sources(&mut self, pair: Pair) -> &mut Self {
         self.app
-            .execute_contract(self.admin.clone(), pool_addr, &swap_msg, &[coin])
+            .execute_contract(
+                self.admin.clone(),
+                self.oracle_addr.clone(),
+                &price_oracle::msg::ExecuteMsg::RemovePriceSources { pair },
+                &[],
+            )
             .unwrap();
 
         self
     }
 
-    pub fn do_random_swap(
+    pub fn update_oracle_twap_config(
         &mut self,
-        rng: &mut ThreadRng,
         pair: Pair,
-        min_limit: u128,
-        max_limit: u128,
-    ) -> &mut Self {
-        let pool_addr = self.astro_pools.get(&pair.clone().into()).unwrap().clone();
-        let swap_amount = rng.gen_range(min_limit..max_limit);
-        let denom_index = rng.gen_range(0..2);
-
-        let coin = match denom_index {
-            0 => Coin {
-                denom: pair.0,
-                amount: Uint128::from(swap_amount),
+        config: TwapConfig,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::UpdateTwapConfig { pair, config },
+            &[],
+        )
+    }
+
+    pub fn update_oracle_max_price_deviation(
+        &mut self,
+        bps: u64,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::UpdateConfig {
+                auction_manager_addr: None,
+                seconds_allow_manual_change: None,
+                seconds_auction_prices_fresh: None,
+                max_price_deviation_bps: Some(bps),
+                anchor_denom: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_oracle_anchor_denom(
+        &mut self,
+        denom: &str,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::UpdateConfig {
+                auction_manager_addr: None,
+                seconds_allow_manual_change: None,
+                seconds_auction_prices_fresh: None,
+                max_price_deviation_bps: None,
+                anchor_denom: Some(denom.to_string()),
+            },
+            &[],
+        )
+    }
+
+    pub fn approve_pending_price(&mut self, pair: Pair) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::ApprovePendingPrice { pair },
+            &[],
+        )
+    }
+
+    pub fn approve_pending_price_err(&mut self, pair: Pair) -> price_oracle::error::ContractError {
+        self.approve_pending_price(pair)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
+    pub fn reject_pending_price(&mut self, pair: Pair) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.oracle_addr.clone(),
+            &price_oracle::msg::ExecuteMsg::RejectPendingPrice { pair },
+            &[],
+        )
+    }
+
+    pub fn set_mock_price(&mut self, mock_addr: Addr, price: Decimal) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                mock_addr,
+                &mock_price_source::ExecuteMsg::SetPrice { price },
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    /// Init a stand-in contract for the osmosis pool, pair simulation and price feed sources
+    pub fn init_mock_price_source(&mut self, price: Decimal) -> Addr {
+        let code_id = self.app.store_code(mock_price_source_contract());
+
+        self.app
+            .instantiate_contract(
+                code_id,
+                self.admin.clone(),
+                &mock_price_source::InstantiateMsg { price },
+                &[],
+                "mock_price_source",
+                None,
+            )
+            .unwrap()
+    }
+
+    pub fn init_mock_cw4_group(&mut self, members: Vec<String>) -> Addr {
+        let code_id = self.app.store_code(mock_cw4_group_contract());
+
+        self.app
+            .instantiate_contract(
+                code_id,
+                self.admin.clone(),
+                &mock_cw4_group::InstantiateMsg { members },
+                &[],
+                "mock_cw4_group",
+                None,
+            )
+            .unwrap()
+    }
+
+    pub fn init_cw20_token(&mut self, initial_balances: Vec<(Addr, u128)>) -> Addr {
+        let code_id = self.app.store_code(cw20_contract());
+
+        self.app
+            .instantiate_contract(
+                code_id,
+                self.admin.clone(),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "Test token".to_string(),
+                    symbol: "TEST".to_string(),
+                    decimals: 6,
+                    initial_balances: initial_balances
+                        .into_iter()
+                        .map(|(address, amount)| Cw20Coin {
+                            address: address.to_string(),
+                            amount: Uint128::new(amount),
+                        })
+                        .collect(),
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "cw20_token",
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Init another oracle contract to be used as a price source
+    pub fn init_second_oracle(&mut self) -> Addr {
+        let code_id = self.app.store_code(oracle_contract());
+        let init_msg: price_oracle::msg::InstantiateMsg =
+            OracleInstantiate::default(self.auctions_manager_addr.clone()).into();
+
+        self.app
+            .instantiate_contract(
+                code_id,
+                self.admin.clone(),
+                &init_msg,
+                &[],
+                "second_oracle",
+                None,
+            )
+            .unwrap()
+    }
+
+    pub fn astro_swap(&mut self, pool_addr: Addr, coin: Coin) -> &mut Self {
+        let offer_asset = astroport::asset::Asset {
+            info: astroport::asset::AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            },
+            amount: coin.amount,
+        };
+
+        let swap_msg = astroport::pair::ExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: Some(Decimal::bps(5000)),
+            to: None,
+        };
+
+        self.app
+            .execute_contract(self.admin.clone(), pool_addr, &swap_msg, &[coin])
+            .unwrap();
+
+        self
+    }
+
+    pub fn do_random_swap(
+        &mut self,
+        rng: &mut ThreadRng,
+        pair: Pair,
+        min_limit: u128,
+        max_limit: u128,
+    ) -> &mut Self {
+        let pool_addr = self.astro_pools.get(&pair.clone().into()).unwrap().clone();
+        let swap_amount = rng.gen_range(min_limit..max_limit);
+        let denom_index = rng.gen_range(0..2);
+
+        let coin = match denom_index {
+            0 => Coin {
+                denom: pair.0,
+                amount: Uint128::from(swap_amount),
             },
             _ => Coin {
                 denom: pair.1,
@@ -622,6 +1112,238 @@ impl Suite {
         self.astro_swap(pool_addr, coin)
     }
 
+    pub fn set_auction_schedule(
+        &mut self,
+        pair: Pair,
+        schedule: AuctionSchedule,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::SetAuctionSchedule { pair, schedule },
+            )),
+            &[],
+        )
+    }
+
+    pub fn update_keeper_tip(&mut self, tip: Option<Coin>) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::UpdateKeeperTip { tip },
+            )),
+            &[],
+        )
+    }
+
+    pub fn open_due_auctions(
+        &mut self,
+        sender: Addr,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            sender,
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::OpenDueAuctions { start_after, limit },
+            &[],
+        )
+    }
+
+    pub fn open_due_auctions_err(
+        &mut self,
+        sender: Addr,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> auctions_manager::error::ContractError {
+        self.open_due_auctions(sender, start_after, limit)
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
+    pub fn deprecate_auction(&mut self, pair: Pair) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::DeprecateAuction { pair },
+            )),
+            &[],
+        )
+    }
+
+    pub fn update_min_amount(&mut self, denom: &str, min_amount: MinAmount) {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::UpdateMinAmount {
+                        denom: denom.to_string(),
+                        min_amount,
+                    },
+                )),
+                &[],
+            )
+            .unwrap();
+    }
+
+    pub fn refund_deprecated_leftovers(
+        &mut self,
+        pair: Pair,
+        limit: Option<u64>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::RefundDeprecatedLeftovers { pair, limit },
+            )),
+            &[],
+        )
+    }
+
+    pub fn remove_auction(&mut self, pair: Pair) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::RemoveAuction { pair },
+            )),
+            &[],
+        )
+    }
+
+    pub fn remove_auction_err(&mut self, pair: Pair) -> auctions_manager::error::ContractError {
+        self.remove_auction(pair).unwrap_err().downcast().unwrap()
+    }
+
+    pub fn set_template_err(
+        &mut self,
+        name: &str,
+        template: AuctionTemplate,
+    ) -> auctions_manager::error::ContractError {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::SetTemplate {
+                        name: name.to_string(),
+                        template,
+                    },
+                )),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    }
+
+    pub fn set_template(&mut self, name: &str, template: AuctionTemplate) -> &mut Self {
+        self.app
+            .execute_contract(
+                self.admin.clone(),
+                self.auctions_manager_addr.clone(),
+                &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                    auctions_manager::msg::AdminMsgs::SetTemplate {
+                        name: name.to_string(),
+                        template,
+                    },
+                )),
+                &[],
+            )
+            .unwrap();
+
+        self
+    }
+
+    pub fn init_auction_from_template(
+        &mut self,
+        pair: Pair,
+        template: &str,
+    ) -> Result<AppResponse, anyhow::Error> {
+        let res = self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::NewAuction {
+                    msg: auction::msg::InstantiateMsg {
+                        pair: pair.clone(),
+                        ..AuctionInstantiate::default().into()
+                    },
+                    label: "label".to_string(),
+                    min_amount: None,
+                    template: Some(template.to_string()),
+                },
+            )),
+            &[],
+        )?;
+
+        let addr: Addr = self.app.wrap().query_wasm_smart(
+            self.auctions_manager_addr.clone(),
+            &AuctionsManagerQueryMsg::GetPairAddr { pair: pair.clone() },
+        )?;
+        self.auction_addrs.insert(pair.into(), addr);
+
+        Ok(res)
+    }
+
+    pub fn apply_template(
+        &mut self,
+        template: &str,
+        pairs: Vec<Pair>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::ApplyTemplate {
+                    template: template.to_string(),
+                    pairs,
+                },
+            )),
+            &[],
+        )
+    }
+
+    pub fn migrate_all_auctions(
+        &mut self,
+        code_id: u64,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            self.admin.clone(),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Admin(Box::new(
+                auctions_manager::msg::AdminMsgs::MigrateAllAuctions {
+                    code_id,
+                    msg: auction::msg::MigrateMsg::NoStateChange {},
+                    start_after,
+                    limit,
+                },
+            )),
+            &[],
+        )
+    }
+
+    pub fn crank(
+        &mut self,
+        pairs: Option<Vec<Pair>>,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        self.app.execute_contract(
+            Addr::unchecked("keeper"),
+            self.auctions_manager_addr.clone(),
+            &auctions_manager::msg::ExecuteMsg::Crank { pairs, limit },
+            &[],
+        )
+    }
+
     pub fn change_server_addr(&mut self, addr: Addr) -> &mut Self {
         self.app
             .execute_contract(
@@ -690,6 +1412,112 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_oracle_price_details(&self, pair: Pair) -> PriceDetailsResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetPriceDetails { pair },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_price_sources(&self, pair: Pair) -> PriceSources {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetPriceSources { pair },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_twap_config(&self, pair: Pair) -> TwapConfig {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetTwapConfig { pair },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_role_members(&self, role: Role) -> Vec<(Addr, RoleScope)> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetRoleMembers {
+                    role,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_pending_price(&self, pair: Pair) -> Option<PendingPrice> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetPendingPrice { pair },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_price_history(
+        &self,
+        pair: Pair,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<(u64, PriceHistoryEntry)> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetPriceHistory {
+                    pair,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_stale_prices(
+        &self,
+        max_age_seconds: u64,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> StalePricesResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetStalePrices {
+                    max_age_seconds,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_oracle_health(
+        &self,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> HealthResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.oracle_addr.clone(),
+                &price_oracle::msg::QueryMsg::GetHealth { start_after, limit },
+            )
+            .unwrap()
+    }
+
     pub fn query_oracle_local_price(&self, pair: Pair) -> Vec<GetPriceResponse> {
         self.app
             .wrap()
@@ -721,6 +1549,112 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_auction_fills(
+        &self,
+        auction_addr: Addr,
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> GetAuctionFillsResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                auction_addr,
+                &auction::msg::QueryMsg::GetAuctionFills {
+                    auction_id,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_provider_history(
+        &self,
+        auction_addr: Addr,
+        addr: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<(u64, ProviderReceipt)> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                auction_addr,
+                &auction::msg::QueryMsg::GetProviderHistory {
+                    addr: addr.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_settlement_mode(&self, auction_addr: Addr) -> SettlementMode {
+        self.app
+            .wrap()
+            .query_wasm_smart(auction_addr, &auction::msg::QueryMsg::GetSettlementMode)
+            .unwrap()
+    }
+
+    pub fn query_is_allowed_bidder(&self, auction_addr: Addr, addr: &Addr) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                auction_addr,
+                &auction::msg::QueryMsg::IsAllowedBidder {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_accrued_fees(&self, auction_addr: Addr) -> Coin {
+        self.app
+            .wrap()
+            .query_wasm_smart(auction_addr, &auction::msg::QueryMsg::GetAccruedFees)
+            .unwrap()
+    }
+
+    pub fn query_dust_providers_total(
+        &self,
+        auction_addr: Addr,
+        auction_id: u64,
+    ) -> Option<DustProvidersTotal> {
+        DUST_PROVIDERS_TOTAL
+            .query(&self.app.wrap(), auction_addr, auction_id)
+            .unwrap()
+    }
+
+    pub fn query_auction_dust(&self, auction_addr: Addr, auction_id: u64) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                auction_addr,
+                &auction::msg::QueryMsg::GetAuctionDust { auction_id },
+            )
+            .unwrap()
+    }
+
+    pub fn query_dust_sweep(&self, auction_addr: Addr) -> Option<DustSweep> {
+        self.app
+            .wrap()
+            .query_wasm_smart(auction_addr, &auction::msg::QueryMsg::GetDustSweep)
+            .unwrap()
+    }
+
+    pub fn query_cw20_balance(&self, token: Addr, addr: Addr) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart::<BalanceResponse>(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                },
+            )
+            .unwrap()
+            .balance
+    }
+
     pub fn query_auction_strategy(&self, auction_addr: Addr) -> AuctionStrategy {
         self.app
             .wrap()
@@ -770,6 +1704,28 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_template(&self, name: &str) -> AuctionTemplate {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.auctions_manager_addr.clone(),
+                &AuctionsManagerQueryMsg::GetTemplate {
+                    name: name.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_migration_progress(&self) -> Option<MigrationProgress> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.auctions_manager_addr.clone(),
+                &AuctionsManagerQueryMsg::GetMigrationProgress,
+            )
+            .unwrap()
+    }
+
     pub fn query_auctions_manager_all_pairs(&self) -> Vec<(Pair, Addr)> {
         self.app
             .wrap()
@@ -783,6 +1739,20 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_all_active_auctions(
+        &self,
+        start_after: Option<Pair>,
+        limit: Option<u32>,
+    ) -> ActiveAuctionsResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.auctions_manager_addr.clone(),
+                &AuctionsManagerQueryMsg::GetAllActiveAuctions { start_after, limit },
+            )
+            .unwrap()
+    }
+
     pub fn query_astro_pool_price(&self, pool_addr: Addr, pair: Pair) -> Decimal {
         let multiply = 1_000_000_u128;
         let res: astroport::pair::SimulationResponse = self
@@ -271,6 +271,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
@@ -289,6 +290,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
@@ -307,6 +309,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
@@ -325,6 +328,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
@@ -343,6 +347,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
@@ -361,6 +366,7 @@ impl SuiteBuilder {
                         send: Uint128::new(5),
                         start_auction: Uint128::new(10),
                     }),
+                    template: None,
                 },
             )),
             &[],
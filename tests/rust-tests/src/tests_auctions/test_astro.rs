@@ -1,7 +1,8 @@
 use std::borrow::BorrowMut;
 
 use auction_package::Pair;
-use price_oracle::state::PriceStep;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use price_oracle::state::{AstroPriceQuery, PriceStep};
 
 use crate::suite::{
     suite::{ATOM, NTRN, OSMO},
@@ -21,6 +22,8 @@ fn test_add_path_for_pair() {
             .get(&suite.pair.clone().into())
             .unwrap()
             .clone(),
+        offer_amount: None,
+        price_query: None,
     }];
     let err = suite.add_astro_path_to_oracle_err(suite.pair.clone(), path);
     assert_eq!(err, price_oracle::error::ContractError::PricePathIsWrong);
@@ -34,6 +37,8 @@ fn test_add_path_for_pair() {
             .get(&suite.pair.clone().into())
             .unwrap()
             .clone(),
+        offer_amount: None,
+        price_query: None,
     }];
     suite
         .add_astro_path_to_oracle(suite.pair.clone(), path)
@@ -63,6 +68,8 @@ fn test_basic_astro_default() {
             .get(&suite.pair.clone().into())
             .unwrap()
             .clone(),
+        offer_amount: None,
+        price_query: None,
     }];
     suite
         .add_astro_path_to_oracle(suite.pair.clone(), path)
@@ -120,6 +127,8 @@ fn test_complex_astro_default() {
                 .get(&suite.pair.clone().into())
                 .unwrap()
                 .clone(),
+            offer_amount: None,
+            price_query: None,
         },
         PriceStep {
             denom1: NTRN.to_string(),
@@ -129,6 +138,8 @@ fn test_complex_astro_default() {
                 .get(&(NTRN.to_string(), OSMO.to_string()))
                 .unwrap()
                 .clone(),
+            offer_amount: None,
+            price_query: None,
         },
     ];
     suite
@@ -183,3 +194,102 @@ fn test_complex_astro_default() {
     // make sure the old price is not the same as new price
     assert_ne!(oracle_price.price, old_oracle_price.price);
 }
+
+#[test]
+fn test_astro_price_queries() {
+    let mut suite = SuiteBuilder::default().build_basic(true);
+    let pair = Pair::from((NTRN.to_string(), OSMO.to_string()));
+    let pool_addr = suite
+        .astro_pools
+        .get(&(NTRN.to_string(), OSMO.to_string()))
+        .unwrap()
+        .clone();
+    let step = |offer_amount: Option<u128>, price_query: Option<AstroPriceQuery>| PriceStep {
+        denom1: NTRN.to_string(),
+        denom2: OSMO.to_string(),
+        pool_address: pool_addr.clone(),
+        offer_amount: offer_amount.map(Uint128::new),
+        price_query,
+    };
+
+    // offer amount can't be zero
+    let err = suite.add_astro_path_to_oracle_err(pair.clone(), vec![step(Some(0), None)]);
+    assert_eq!(err, price_oracle::error::ContractError::PricePathIsWrong);
+
+    // Spot price is the ratio of the pool reserves (1000 NTRN / 2000 OSMO)
+    suite
+        .add_astro_path_to_oracle(pair.clone(), vec![step(None, Some(AstroPriceQuery::Spot))])
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    let spot_price = suite.query_oracle_local_price(pair.clone())[0].price;
+    assert_eq!(spot_price, Decimal::from_ratio(2_u128, 1_u128));
+
+    // Default simulation adds the spread back, so the offer amount doesn't change the price
+    suite
+        .update_astro_path_in_oracle(pair.clone(), vec![step(None, None)])
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        suite.query_astro_pool_price(pool_addr.clone(), pair.clone())
+    );
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        spot_price
+    );
+
+    // Excluding the spread, a bigger offer gets a lower price
+    let excluding_spread = Some(AstroPriceQuery::SimulationExcludingSpread);
+    suite
+        .update_astro_path_in_oracle(pair.clone(), vec![step(None, excluding_spread.clone())])
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    let small_offer_price = suite.query_oracle_local_price(pair.clone())[0].price;
+    assert!(small_offer_price < spot_price);
+
+    suite
+        .update_astro_path_in_oracle(
+            pair.clone(),
+            vec![step(Some(100_000_000), excluding_spread)],
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    let big_offer_price = suite.query_oracle_local_price(pair.clone())[0].price;
+    assert!(big_offer_price < small_offer_price);
+
+    // First cumulative update has no previous cumulative price, so we get the spot price
+    suite
+        .update_astro_path_in_oracle(
+            pair.clone(),
+            vec![step(None, Some(AstroPriceQuery::Cumulative))],
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        spot_price
+    );
+
+    // Swap OSMO into the pool half way, to increase the price of NTRN
+    suite.update_block(10);
+    suite.astro_swap(pool_addr.clone(), coin(200_000_000_u128, OSMO));
+    suite.update_block(10);
+
+    suite
+        .update_astro_path_in_oracle(pair.clone(), vec![step(None, Some(AstroPriceQuery::Spot))])
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    let new_spot_price = suite.query_oracle_local_price(pair.clone())[0].price;
+
+    // The cumulative price is the average between the old and new spot prices
+    suite
+        .update_astro_path_in_oracle(
+            pair.clone(),
+            vec![step(None, Some(AstroPriceQuery::Cumulative))],
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    let cumulative_price = suite.query_oracle_local_price(pair.clone())[0].price;
+    assert!(cumulative_price > spot_price);
+    assert!(cumulative_price < new_spot_price);
+}
@@ -2,18 +2,26 @@ use core::panic;
 
 use auction::{
     msg::NewAuctionParams,
-    state::{ActiveAuction, ActiveAuctionStatus},
+    state::{ActiveAuction, ActiveAuctionStatus, ProviderReceipt},
+};
+use auction_package::{
+    error::AuctionError,
+    states::{MinAmount, TWAP_PRICES},
+    AuctionStrategy, BidderGate, Pair, ProtocolFee, SettlementMode,
 };
-use auction_package::{error::AuctionError, states::TWAP_PRICES};
 use cosmwasm_std::{
-    coin, coins, from_json, testing::mock_env, Addr, Binary, Decimal, Timestamp, Uint128,
+    coin, coins, from_json, testing::mock_env, to_json_binary, Addr, Binary, Decimal, Timestamp,
+    Uint128,
 };
 use cw_multi_test::Executor;
 use price_oracle::state::PriceStep;
 use valence_package::event_indexing::ValenceEvent;
 
-use crate::suite::suite::{
-    Suite, DAY, DEFAULT_BALANCE_AMOUNT, DEFAULT_BLOCK_TIME, DEFAULT_NTRN_PRICE_BPS,
+use crate::suite::{
+    instantiates::AuctionInstantiate,
+    mock_cw4_group,
+    suite::{Suite, DAY, DEFAULT_BALANCE_AMOUNT, DEFAULT_BLOCK_TIME, DEFAULT_NTRN_PRICE_BPS},
+    suite_builder::SuiteBuilder,
 };
 
 #[test]
@@ -54,7 +62,8 @@ fn test_open_auction() {
             resolved_amount: Uint128::zero(),
             total_amount: funds[0].amount,
             leftovers: [Uint128::zero(), Uint128::zero()],
-            last_checked_block: mock_env().block
+            last_checked_block: mock_env().block,
+            reserve_price: None,
         }
     );
 }
@@ -230,6 +239,547 @@ fn test_bid() {
     assert_eq!(active_auction.status, ActiveAuctionStatus::Finished)
 }
 
+#[test]
+fn test_auction_fills() {
+    let mut suite = Suite::default();
+    let funds = coins(1000_u128, suite.pair.0.clone());
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        &funds,
+    );
+
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            Some(mock_env().block.height),
+            mock_env().block.height + 1000,
+        )
+        .unwrap();
+
+    let price_per_block = suite.calc_price_per_block(suite.get_default_auction_addr());
+    let start_price = Decimal::bps(DEFAULT_NTRN_PRICE_BPS)
+        + Decimal::bps(DEFAULT_NTRN_PRICE_BPS) * Decimal::bps(2000);
+
+    // buy 250 atom
+    suite.update_block(100);
+    let block_price1 = start_price - price_per_block * Decimal::from_atomics(100_u128, 0).unwrap();
+    let ntrn_to_send1 = (Decimal::from_atomics(250_u128, 0).unwrap() * block_price1).to_uint_ceil();
+    suite
+        .do_bid(
+            suite.pair.clone(),
+            coin(ntrn_to_send1.u128(), suite.pair.1.clone()),
+        )
+        .unwrap();
+
+    // buy 250 more atom
+    suite.update_block(100);
+    let block_price2 = start_price - price_per_block * Decimal::from_atomics(200_u128, 0).unwrap();
+    let ntrn_to_send2 = (Decimal::from_atomics(250_u128, 0).unwrap() * block_price2).to_uint_ceil();
+    suite
+        .do_bid(
+            suite.pair.clone(),
+            coin(ntrn_to_send2.u128(), suite.pair.1.clone()),
+        )
+        .unwrap();
+
+    let res = suite.query_auction_fills(suite.get_default_auction_addr(), 1, None, None);
+    assert_eq!(res.fills.len(), 2);
+
+    let (index, fill) = res.fills[0].clone();
+    assert_eq!(index, 0);
+    assert_eq!(fill.bidder, suite.mm);
+    assert_eq!(fill.block, mock_env().block.height + 100);
+    assert_eq!(fill.price, block_price1);
+    assert_eq!(fill.bought_amount, Uint128::from(250_u128));
+
+    let active_auction = suite.query_auction_details(suite.get_default_auction_addr());
+    let total_paid = res.fills[0].1.paid_amount + res.fills[1].1.paid_amount;
+    assert_eq!(res.total_resolved, total_paid);
+    assert_eq!(res.total_resolved, active_auction.resolved_amount);
+    assert_eq!(
+        res.vwap,
+        Decimal::from_ratio(total_paid, Uint128::from(500_u128))
+    );
+    assert_eq!(res.fill_ratio, Decimal::percent(50));
+
+    // Paginate over the fills
+    let res = suite.query_auction_fills(suite.get_default_auction_addr(), 1, Some(0), None);
+    assert_eq!(res.fills.len(), 1);
+    assert_eq!(res.fills[0].0, 1);
+    assert_eq!(res.fills[0].1.price, block_price2);
+}
+
+#[test]
+fn test_provider_history() {
+    let mut suite = Suite::default();
+    let funds = coins(100_u128, suite.pair.0.clone());
+
+    // do 2 auctions
+    suite.finalize_auction(&funds);
+    let resolved_amount1 = suite
+        .query_auction_details(suite.get_default_auction_addr())
+        .resolved_amount;
+    suite.clean_last_auction(suite.get_default_auction_addr());
+
+    suite.finalize_auction(&funds);
+
+    let history = suite.query_provider_history(
+        suite.get_default_auction_addr(),
+        suite.get_account_addr(0),
+        None,
+        None,
+    );
+    assert_eq!(history.len(), 2);
+
+    // Receipt is kept even after the auction storage is cleaned
+    let (auction_id, receipt) = history[0].clone();
+    assert_eq!(auction_id, 1);
+    assert_eq!(
+        receipt,
+        ProviderReceipt {
+            sent: funds[0].amount,
+            received: resolved_amount1,
+            leftover: Uint128::zero(),
+        }
+    );
+
+    let history = suite.query_provider_history(
+        suite.get_default_auction_addr(),
+        suite.get_account_addr(0),
+        Some(1),
+        None,
+    );
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, 2);
+}
+
+#[test]
+fn test_claim_settlement() {
+    let mut suite = Suite::default();
+    let funds = coins(100_u128, suite.pair.0.clone());
+
+    suite.update_settlement_mode(suite.pair.clone(), SettlementMode::Claim);
+    assert_eq!(
+        suite.query_settlement_mode(suite.get_default_auction_addr()),
+        SettlementMode::Claim
+    );
+
+    let balance_before = suite.get_balance(0, &suite.pair.1.clone());
+    suite.finalize_auction(&funds);
+
+    // Auction is closed, but nothing was sent to the provider yet
+    suite.assert_auction_status(ActiveAuctionStatus::AuctionClosed);
+    let resolved_amount = suite
+        .query_auction_details(suite.get_default_auction_addr())
+        .resolved_amount;
+    assert_eq!(suite.get_balance(0, &suite.pair.1.clone()), balance_before);
+
+    // Cleaning the auction should keep the funds claimable
+    suite.clean_last_auction(suite.get_default_auction_addr());
+
+    suite
+        .claim(
+            suite.get_account_addr(0),
+            suite.get_default_auction_addr(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.get_balance(0, &suite.pair.1.clone()).amount,
+        balance_before.amount + resolved_amount
+    );
+
+    let history = suite.query_provider_history(
+        suite.get_default_auction_addr(),
+        suite.get_account_addr(0),
+        None,
+        None,
+    );
+    assert_eq!(
+        history,
+        vec![(
+            1,
+            ProviderReceipt {
+                sent: funds[0].amount,
+                received: resolved_amount,
+                leftover: Uint128::zero(),
+            }
+        )]
+    );
+
+    // Can't claim twice
+    let err = suite.claim_err(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        1,
+    );
+    assert_eq!(err, auction::error::ContractError::NothingToClaim);
+
+    // Can't claim auction that wasn't settled in claim mode
+    let err = suite.claim_err(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        2,
+    );
+    assert_eq!(err, auction::error::ContractError::NoClaimableAuction(2));
+}
+
+#[test]
+fn test_protocol_fee() {
+    let mut suite = Suite::default();
+    let funds = coins(100_u128, suite.pair.0.clone());
+    let treasury = Addr::unchecked("treasury");
+
+    let err: auction::error::ContractError = suite
+        .update_protocol_fee(
+            suite.pair.clone(),
+            Some(ProtocolFee {
+                bps: 10001,
+                treasury: treasury.clone(),
+            }),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, AuctionError::InvalidProtocolFee.into());
+
+    // 5% fee
+    suite
+        .update_protocol_fee(
+            suite.pair.clone(),
+            Some(ProtocolFee {
+                bps: 500,
+                treasury: treasury.clone(),
+            }),
+        )
+        .unwrap();
+
+    suite.finalize_auction(&funds);
+    let resolved_amount = suite
+        .query_auction_details(suite.get_default_auction_addr())
+        .resolved_amount;
+    let fee_amount = resolved_amount * Decimal::bps(500);
+    assert!(!fee_amount.is_zero());
+
+    let treasury_balance = suite
+        .app
+        .wrap()
+        .query_balance(treasury, suite.pair.1.clone())
+        .unwrap();
+    assert_eq!(treasury_balance.amount, fee_amount);
+    assert_eq!(
+        suite.query_accrued_fees(suite.get_default_auction_addr()),
+        coin(fee_amount.u128(), suite.pair.1.clone())
+    );
+
+    // The provider receives the resolved amount without the fee
+    let history = suite.query_provider_history(
+        suite.get_default_auction_addr(),
+        suite.get_account_addr(0),
+        None,
+        None,
+    );
+    assert_eq!(history[0].1.received, resolved_amount - fee_amount);
+
+    // Nothing is left for the next auction
+    let auction = suite.query_auction_details(suite.get_default_auction_addr());
+    assert_eq!(auction.leftovers, [Uint128::zero(), Uint128::zero()]);
+
+    // Removing the fee stops taking it
+    suite.update_protocol_fee(suite.pair.clone(), None).unwrap();
+    suite.clean_last_auction(suite.get_default_auction_addr());
+    suite.finalize_auction(&funds);
+    assert_eq!(
+        suite.query_accrued_fees(suite.get_default_auction_addr()),
+        coin(fee_amount.u128(), suite.pair.1.clone())
+    );
+}
+
+#[test]
+fn test_bidder_allowlist() {
+    let mut suite = SuiteBuilder::default().with_accounts(2).build_default();
+    let funds = coins(100_u128, suite.pair.0.clone());
+    let auction_addr = suite.get_default_auction_addr();
+    let bid = coin(10_u128, suite.pair.1.clone());
+
+    suite.auction_funds(suite.get_account_addr(0), auction_addr.clone(), &funds);
+    suite.start_auction_day(suite.pair.clone()).unwrap();
+    suite.update_block(1);
+
+    // Only addresses on the allowlist can bid
+    suite.update_bidder_gate(suite.pair.clone(), Some(BidderGate::Allowlist));
+    assert!(!suite.query_is_allowed_bidder(auction_addr.clone(), &suite.mm));
+    let err = suite.do_bid_err(suite.pair.clone(), bid.clone());
+    assert_eq!(err, auction::error::ContractError::UnauthorizedToBid);
+
+    suite.update_bidder_allowlist(suite.pair.clone(), vec![suite.mm.to_string()], vec![]);
+    let allowlist: Vec<Addr> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            auction_addr.clone(),
+            &auction::msg::QueryMsg::GetBidderAllowlist {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(allowlist, vec![suite.mm.clone()]);
+    suite.do_bid(suite.pair.clone(), bid.clone()).unwrap();
+
+    // Only members of the cw4 group can bid
+    let group = suite.init_mock_cw4_group(vec![]);
+    suite.update_bidder_gate(
+        suite.pair.clone(),
+        Some(BidderGate::Cw4Group(group.clone())),
+    );
+    let err = suite.do_bid_err(suite.pair.clone(), bid.clone());
+    assert_eq!(err, auction::error::ContractError::UnauthorizedToBid);
+
+    suite
+        .app
+        .execute_contract(
+            suite.admin.clone(),
+            group,
+            &mock_cw4_group::ExecuteMsg::UpdateMembers {
+                add: vec![suite.mm.to_string()],
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    suite.do_bid(suite.pair.clone(), bid.clone()).unwrap();
+
+    // Anyone can bid once the gate is removed
+    suite.update_bidder_gate(suite.pair.clone(), None);
+    assert!(suite.query_is_allowed_bidder(auction_addr, &suite.get_account_addr(1)));
+}
+
+#[test]
+fn test_reclaim_stuck_funds() {
+    let mut suite = SuiteBuilder::default().with_accounts(2).build_default();
+    let pair = suite.pair.clone();
+    let auction_addr = suite.get_default_auction_addr();
+    let funds = coins(1000_u128, pair.0.clone());
+    let threshold = auction::contract::DEFAULT_RECLAIM_THRESHOLD;
+
+    suite.auction_funds(suite.get_account_addr(0), auction_addr.clone(), &funds);
+    suite.auction_funds(suite.get_account_addr(1), auction_addr.clone(), &funds);
+    suite.start_auction_day(pair.clone()).unwrap();
+    suite.update_block(10);
+    suite
+        .do_bid(pair.clone(), coin(500_u128, pair.1.clone()))
+        .unwrap();
+
+    // Auction is running, funds are not stuck
+    let err = suite.reclaim_funds_err(pair.clone(), suite.get_account_addr(0));
+    assert_eq!(err, auction::error::ContractError::FundsNotStuck(threshold));
+
+    suite.pause_auction(pair.clone());
+    suite.update_block(10);
+    let err = suite.reclaim_funds_err(pair.clone(), suite.get_account_addr(0));
+    assert_eq!(err, auction::error::ContractError::FundsNotStuck(threshold));
+
+    // Paused for longer than the threshold
+    suite.update_block(threshold / DEFAULT_BLOCK_TIME);
+    let auction_before = suite.query_auction_details(auction_addr.clone());
+    let bought_before = suite
+        .query_auction_fills(auction_addr.clone(), 1, None, None)
+        .fills
+        .iter()
+        .fold(Uint128::zero(), |total, (_, fill)| {
+            total + fill.bought_amount
+        });
+    let atom_before = suite.get_balance(0, &pair.0);
+    let ntrn_before = suite.get_balance(0, &pair.1);
+    suite
+        .reclaim_funds(pair.clone(), suite.get_account_addr(0))
+        .unwrap();
+
+    let receipt =
+        suite.query_provider_history(auction_addr.clone(), suite.get_account_addr(0), None, None)
+            [0]
+        .1
+        .clone();
+    assert_eq!(receipt.sent, funds[0].amount);
+    assert!(!receipt.received.is_zero());
+    assert!(!receipt.leftover.is_zero());
+    assert_eq!(
+        suite.get_balance(0, &pair.0).amount,
+        atom_before.amount + receipt.leftover
+    );
+    assert_eq!(
+        suite.get_balance(0, &pair.1).amount,
+        ntrn_before.amount + receipt.received
+    );
+
+    // The share of the other provider stays the same
+    let auction = suite.query_auction_details(auction_addr.clone());
+    assert_eq!(
+        auction.total_amount,
+        auction_before.total_amount - funds[0].amount
+    );
+    assert_eq!(
+        auction.available_amount,
+        auction_before.available_amount - receipt.leftover
+    );
+    assert_eq!(
+        auction.resolved_amount,
+        auction_before.resolved_amount - receipt.received
+    );
+
+    // The fill ratio is of the funds left in the auction
+    assert_eq!(
+        suite
+            .query_auction_fills(auction_addr.clone(), 1, None, None)
+            .fill_ratio,
+        Decimal::from_ratio(
+            bought_before - (receipt.sent - receipt.leftover),
+            auction.total_amount
+        )
+    );
+
+    let err = suite.reclaim_funds_err(pair.clone(), suite.get_account_addr(0));
+    assert_eq!(err, auction::error::ContractError::NoFundsToReclaim);
+
+    // Resumed auction is not stuck anymore
+    suite.resume_auction(pair.clone()).unwrap();
+    let err = suite.reclaim_funds_err(pair.clone(), suite.get_account_addr(1));
+    assert_eq!(err, auction::error::ContractError::FundsNotStuck(threshold));
+
+    // Chain halted for longer than the threshold
+    suite.app.update_block(|b| {
+        b.height += 1;
+        b.time = b.time.plus_seconds(threshold + DAY);
+    });
+    suite
+        .reclaim_funds(pair.clone(), suite.get_account_addr(1))
+        .unwrap();
+    assert!(suite
+        .query_auction_details(auction_addr)
+        .total_amount
+        .is_zero());
+}
+
+#[test]
+fn test_reclaim_paused_without_pause_time() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+    let auction_addr = suite.get_default_auction_addr();
+    let threshold = auction::contract::DEFAULT_RECLAIM_THRESHOLD;
+
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(1000_u128, pair.0.clone()),
+    );
+    suite.start_auction_day(pair.clone()).unwrap();
+    suite.pause_auction(pair.clone());
+
+    // Auctions paused before the pause time was saved count from the last checked block
+    suite
+        .app
+        .contract_storage_mut(&auction_addr)
+        .remove(b"paused_at");
+    suite.update_block(threshold / DEFAULT_BLOCK_TIME + 1);
+
+    suite
+        .reclaim_funds(pair.clone(), suite.get_account_addr(0))
+        .unwrap();
+    assert!(suite
+        .query_auction_details(auction_addr)
+        .total_amount
+        .is_zero());
+}
+
+#[test]
+fn test_reserve_price() {
+    let mut suite = Suite::default();
+    let funds = coins(1000_u128, suite.pair.0.clone());
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        &funds,
+    );
+
+    // Start price is 1.8, end price is 1.2, price drops by 0.0006 every block
+    suite
+        .start_auction_with_params(
+            suite.pair.clone(),
+            NewAuctionParams {
+                start_block: Some(mock_env().block.height),
+                end_block: mock_env().block.height + 1000,
+                reserve_price: Some(Decimal::bps(15000)),
+            },
+        )
+        .unwrap();
+
+    // Price is 1.74, above the reserve price
+    suite.update_block(100);
+    suite
+        .do_bid(suite.pair.clone(), coin(174_u128, suite.pair.1.clone()))
+        .unwrap();
+
+    // Price is 1.44, below the reserve price
+    suite.update_block(500);
+    let err = suite.do_bid_err(suite.pair.clone(), coin(144_u128, suite.pair.1.clone()));
+    assert_eq!(
+        err,
+        auction::error::ContractError::PriceBelowReserve(Decimal::bps(15000))
+    );
+
+    // We can finish the auction before the end block,
+    // unsold funds are added to the provider funds of the next auction
+    let pair0_before = suite.get_balance(0, &suite.pair.0.clone());
+    let pair1_before = suite.get_balance(0, &suite.pair.1.clone());
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    let active_auction = suite.query_auction_details(suite.get_default_auction_addr());
+    assert_eq!(active_auction.status, ActiveAuctionStatus::AuctionClosed);
+    assert_eq!(active_auction.leftovers[0], Uint128::zero());
+
+    // Provider only received the bought amount, the unsold amount rolls into the next auction
+    assert_eq!(suite.get_balance(0, &suite.pair.0.clone()), pair0_before);
+    assert_eq!(
+        suite.get_balance(0, &suite.pair.1.clone()).amount,
+        pair1_before.amount + Uint128::new(174)
+    );
+    let provider_funds =
+        suite.query_auction_funds(suite.get_account_addr(0), suite.get_default_auction_addr());
+    assert_eq!(provider_funds.next, Uint128::new(900));
+
+    let history = suite.query_provider_history(
+        suite.get_default_auction_addr(),
+        suite.get_account_addr(0),
+        None,
+        None,
+    );
+    assert_eq!(
+        history[0].1,
+        ProviderReceipt {
+            sent: funds[0].amount,
+            received: Uint128::new(174),
+            leftover: Uint128::new(900),
+        }
+    );
+
+    // Next auction includes the rolled over funds
+    suite.set_balance(0, coin(100_u128, suite.pair.0.clone()));
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        &coins(100_u128, suite.pair.0.clone()),
+    );
+    suite.start_auction_day(suite.pair.clone()).unwrap();
+
+    let active_auction = suite.query_auction_details(suite.get_default_auction_addr());
+    assert_eq!(active_auction.available_amount, Uint128::new(1000));
+    let provider_funds =
+        suite.query_auction_funds(suite.get_account_addr(0), suite.get_default_auction_addr());
+    assert_eq!(provider_funds.curr, Uint128::new(1000));
+}
+
 #[test]
 fn test_exact_bid() {
     let mut suite = Suite::default();
@@ -640,6 +1190,8 @@ fn test_saving_10_twap_prices() {
             .get(&suite.pair.clone().into())
             .unwrap()
             .clone(),
+        offer_amount: None,
+        price_query: None,
     }];
     suite
         .add_astro_path_to_oracle(suite.pair.clone(), path)
@@ -1005,6 +1557,7 @@ fn test_only_server_can_open_auction() {
                     params: NewAuctionParams {
                         start_block: None,
                         end_block: suite.app.block_info().height + 1000,
+                        reserve_price: None,
                     },
                 },
             ),
@@ -1016,3 +1569,95 @@ fn test_only_server_can_open_auction() {
 
     assert_eq!(err, auctions_manager::error::ContractError::NotServer)
 }
+
+#[test]
+fn test_cw20_auction() {
+    let mut suite = Suite::default();
+    let provider = suite.get_account_addr(0);
+    let token = suite.init_cw20_token(vec![(provider.clone(), 10_000)]);
+    let pair = Pair(token.to_string(), suite.pair.1.clone());
+
+    let mut init_msg = AuctionInstantiate::new(
+        pair.clone(),
+        AuctionStrategy {
+            start_price_perc: 2000,
+            end_price_perc: 2000,
+        },
+    );
+    init_msg.change_cw20_denoms(vec![token.to_string()]);
+    suite.init_auction(
+        pair.clone(),
+        init_msg.into(),
+        Some(MinAmount {
+            send: Uint128::new(5),
+            start_auction: Uint128::new(10),
+        }),
+    );
+    let auction_addr = suite
+        .auction_addrs
+        .get(&pair.clone().into())
+        .unwrap()
+        .clone();
+
+    // Send the tokens through the auctions manager
+    suite
+        .auction_funds_cw20(provider.clone(), token.clone(), pair.clone(), 1000)
+        .unwrap();
+    assert_eq!(
+        suite.query_cw20_balance(token.clone(), auction_addr.clone()),
+        Uint128::new(1000)
+    );
+
+    // pair.0 tokens can't be used to bid
+    let err: auction::error::ContractError = suite
+        .app
+        .execute_contract(
+            provider.clone(),
+            token.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: auction_addr.to_string(),
+                amount: Uint128::new(100),
+                msg: to_json_binary(&auction::msg::Cw20HookMsg::Bid {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        auction::error::ContractError::InvalidCw20Token(token.to_string())
+    );
+
+    suite
+        .manual_update_price(pair.clone(), Decimal::one())
+        .unwrap();
+    suite.start_auction_day(pair.clone()).unwrap();
+    suite.update_block(10);
+
+    // Bidder receives the bought tokens
+    suite
+        .do_bid(pair.clone(), coin(500_u128, pair.1.clone()))
+        .unwrap();
+    let bought = suite.query_cw20_balance(token.clone(), suite.mm.clone());
+    assert!(!bought.is_zero());
+
+    // Provider receives the unsold tokens and the resolved amount
+    let ntrn_before = suite.get_balance(0, &pair.1);
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.close_auction(pair.clone(), None).unwrap();
+
+    let auction = suite.query_auction_details(auction_addr.clone());
+    assert_eq!(
+        suite.query_cw20_balance(token.clone(), provider),
+        Uint128::new(10_000) - bought
+    );
+    assert_eq!(
+        suite.get_balance(0, &pair.1).amount,
+        ntrn_before.amount + auction.resolved_amount
+    );
+    assert_eq!(
+        suite.query_cw20_balance(token, auction_addr),
+        Uint128::zero()
+    );
+}
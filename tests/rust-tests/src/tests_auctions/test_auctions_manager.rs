@@ -1,17 +1,23 @@
 use std::str::FromStr;
 
+use auction::state::ActiveAuctionStatus;
 use auction_package::{
     error::AuctionError,
-    helpers::{ChainHaltConfig, GetPriceResponse},
-    Pair, PriceFreshnessStrategy,
+    helpers::{
+        AuctionSchedule, AuctionTemplate, ChainHaltConfig, GetPriceResponse, MigrationProgress,
+    },
+    states::MinAmount,
+    AuctionStrategy, DustDestination, Pair, PriceFreshnessStrategy, SettlementMode,
 };
-use cosmwasm_std::{coins, Addr, Decimal};
+use cosmwasm_std::{coin, coins, Addr, Coin, Decimal, Event, Uint128};
 use cw_multi_test::Executor;
 use cw_utils::Expiration;
+use valence_package::event_indexing::ValenceEvent;
 
 use crate::suite::{
+    contracts::auction_contract,
     instantiates::AuctionInstantiate,
-    suite::{Suite, DEFAULT_BLOCK_TIME},
+    suite::{Suite, ATOM, DAY, DEFAULT_BLOCK_TIME, NTRN, OSMO},
     suite_builder::SuiteBuilder,
 };
 
@@ -318,3 +324,782 @@ fn test_change_server_addr() {
     let server_addr = suite.query_server_addr();
     assert_eq!(server_addr, new_server_addr);
 }
+
+#[test]
+fn test_get_all_active_auctions() {
+    let mut suite = Suite::default();
+    let funds = coins(500_u128, suite.pair.0.clone());
+    let pair2 = Pair::from((ATOM.to_string(), OSMO.to_string()));
+
+    // No auction is opened yet
+    let res = suite.query_all_active_auctions(None, None);
+    assert!(res.auctions.is_empty());
+    assert_eq!(res.last_scanned, None);
+
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        suite.get_default_auction_addr(),
+        &funds,
+    );
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        suite
+            .auction_addrs
+            .get(&pair2.clone().into())
+            .unwrap()
+            .clone(),
+        &funds,
+    );
+    suite.start_auction_day(suite.pair.clone()).unwrap();
+    suite.start_auction_day(pair2.clone()).unwrap();
+
+    suite.update_block(100);
+
+    let auctions = suite.query_all_active_auctions(None, None).auctions;
+    assert_eq!(auctions.len(), 2);
+
+    let auction = auctions.iter().find(|a| a.pair == suite.pair).unwrap();
+    let auction_price = suite.query_auction_price(suite.get_default_auction_addr());
+    assert_eq!(auction.addr, suite.get_default_auction_addr());
+    assert_eq!(auction.status, ActiveAuctionStatus::Started);
+    assert_eq!(auction.price, auction_price.price);
+    assert_eq!(auction.available_amount, funds[0].amount);
+
+    // Paginate over the pairs, the limit is the number of pairs scanned,
+    // so a page can be empty while there are still active auctions to find
+    let pairs = suite.query_auctions_manager_all_pairs();
+    let mut start_after = None;
+    let mut pages = 0;
+    let mut paged_auctions = vec![];
+    loop {
+        let page = suite.query_all_active_auctions(start_after, Some(1));
+        assert!(page.auctions.len() <= 1);
+        paged_auctions.extend(page.auctions);
+        pages += 1;
+
+        match page.last_scanned {
+            Some(last_scanned) => start_after = Some(last_scanned),
+            None => break,
+        }
+    }
+    // One page per pair, and one more page that finds no pairs
+    assert_eq!(pages, pairs.len() + 1);
+    assert_eq!(paged_auctions, auctions);
+
+    // Close the auction, it should not be active anymore
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    let auctions = suite.query_all_active_auctions(None, None).auctions;
+    assert_eq!(auctions.len(), 1);
+    assert_eq!(auctions[0].pair, pair2);
+}
+
+#[test]
+fn test_open_due_auctions() {
+    let mut suite = Suite::default();
+    let keeper = Addr::unchecked("keeper");
+    let auction_addr = suite.get_default_auction_addr();
+    let schedule = AuctionSchedule {
+        duration_blocks: 100,
+        interval_blocks: 1000,
+        min_funds: Uint128::new(500),
+        reserve_price: Some(Decimal::bps(15000)),
+    };
+
+    suite
+        .set_auction_schedule(suite.pair.clone(), schedule.clone())
+        .unwrap();
+
+    // Not enough funds for the auction yet
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(400_u128, suite.pair.0.clone()),
+    );
+    let err = suite.open_due_auctions_err(keeper.clone(), None, None);
+    assert_eq!(err, auctions_manager::error::ContractError::NoDueAuctions);
+
+    suite.auction_funds(
+        suite.admin.clone(),
+        auction_addr.clone(),
+        &coins(400_u128, suite.pair.0.clone()),
+    );
+
+    // Tip the keeper from the manager balance
+    let tip = coin(10_u128, NTRN);
+    suite.update_keeper_tip(Some(tip.clone())).unwrap();
+    suite
+        .app
+        .send_tokens(
+            suite.admin.clone(),
+            suite.auctions_manager_addr.clone(),
+            &[tip.clone()],
+        )
+        .unwrap();
+
+    suite.open_due_auctions(keeper.clone(), None, None).unwrap();
+
+    let auction = suite.query_auction_details(auction_addr.clone());
+    assert_eq!(auction.status, ActiveAuctionStatus::Started);
+    assert_eq!(
+        auction.end_block,
+        suite.app.block_info().height + schedule.duration_blocks
+    );
+    assert_eq!(auction.reserve_price, schedule.reserve_price);
+    assert_eq!(suite.app.wrap().query_balance(&keeper, NTRN).unwrap(), tip);
+
+    // Finish the auction, the next auction is only due after the interval
+    suite.update_block(schedule.duration_blocks);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(500_u128, suite.pair.0.clone()),
+    );
+
+    let err = suite.open_due_auctions_err(keeper.clone(), None, None);
+    assert_eq!(err, auctions_manager::error::ContractError::NoDueAuctions);
+
+    suite.update_block(schedule.interval_blocks);
+    suite.open_due_auctions(keeper.clone(), None, None).unwrap();
+
+    // The manager has no funds left for the tip, so the auction is opened without it
+    assert_eq!(suite.app.wrap().query_balance(&keeper, NTRN).unwrap(), tip);
+    assert_eq!(
+        suite.query_auction_details(auction_addr).status,
+        ActiveAuctionStatus::Started
+    );
+}
+
+#[test]
+fn test_open_due_auctions_isolation() {
+    let mut suite = SuiteBuilder::default().build_basic(false);
+    let keeper = Addr::unchecked("keeper");
+    let pair1 = suite.pair.clone();
+    let pair2 = Pair::from((ATOM.to_string(), OSMO.to_string()));
+    let auction1_addr = suite.get_default_auction_addr();
+    let auction2_addr = suite.auction_addrs[&pair2.clone().into()].clone();
+    let schedule = AuctionSchedule {
+        duration_blocks: 100,
+        interval_blocks: 1000,
+        min_funds: Uint128::one(),
+        reserve_price: None,
+    };
+
+    suite
+        .set_auction_schedule(pair1.clone(), schedule.clone())
+        .unwrap();
+    suite.set_auction_schedule(pair2.clone(), schedule).unwrap();
+
+    // Below the start auction minimum the auction would refund the funds, so it's not due
+    suite.auction_funds(
+        suite.admin.clone(),
+        auction1_addr.clone(),
+        &coins(6_u128, ATOM),
+    );
+    let err = suite.open_due_auctions_err(keeper.clone(), None, None);
+    assert_eq!(err, auctions_manager::error::ContractError::NoDueAuctions);
+
+    suite.auction_funds(
+        suite.admin.clone(),
+        auction1_addr.clone(),
+        &coins(6_u128, ATOM),
+    );
+    suite.auction_funds(
+        suite.admin.clone(),
+        auction2_addr.clone(),
+        &coins(12_u128, ATOM),
+    );
+
+    // Only the first pair has a price, opening the second pair fails without reverting the first
+    suite
+        .manual_update_price(pair1.clone(), Decimal::one())
+        .unwrap();
+    let res = suite.open_due_auctions(keeper.clone(), None, None).unwrap();
+
+    let opened_event: Event = ValenceEvent::AuctionManagerOpenScheduledAuction {
+        pair: pair1.clone(),
+        tip: None,
+    }
+    .into();
+    assert!(
+        res.has_event(&Event::new("wasm-valence-event").add_attributes(opened_event.attributes))
+    );
+    assert!(res.events.iter().any(|event| {
+        event.ty == "wasm-valence-event"
+            && event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "auction-manager-step-failed")
+    }));
+
+    assert_eq!(
+        suite.query_auction_details(auction1_addr).status,
+        ActiveAuctionStatus::Started
+    );
+    assert_ne!(
+        suite.query_auction_details(auction2_addr.clone()).status,
+        ActiveAuctionStatus::Started
+    );
+
+    // The failed pair is opened once it has a price, paging over the first pair
+    suite
+        .manual_update_price(pair2.clone(), Decimal::one())
+        .unwrap();
+    suite
+        .open_due_auctions(keeper, Some(pair1), Some(1))
+        .unwrap();
+    assert_eq!(
+        suite.query_auction_details(auction2_addr).status,
+        ActiveAuctionStatus::Started
+    );
+}
+
+#[test]
+fn test_crank() {
+    let mut suite = Suite::default();
+    let auction_addr = suite.get_default_auction_addr();
+    let pair = vec![suite.pair.clone()];
+    let pairs = Some(pair.clone());
+    let schedule = AuctionSchedule {
+        duration_blocks: 100,
+        interval_blocks: 1000,
+        min_funds: Uint128::new(500),
+        reserve_price: None,
+    };
+    let crank_event = |finished: &[Pair], cleaned: &[Pair], opened: &[Pair]| {
+        let event: Event = ValenceEvent::AuctionManagerCrank {
+            finished: finished.to_vec(),
+            swept: vec![],
+            cleaned: cleaned.to_vec(),
+            opened: opened.to_vec(),
+        }
+        .into();
+        Event::new("wasm-valence-event").add_attributes(event.attributes)
+    };
+
+    let err: auctions_manager::error::ContractError =
+        suite.crank(None, Some(0)).unwrap_err().downcast().unwrap();
+    assert_eq!(err, auctions_manager::error::ContractError::LimitIsZero);
+
+    suite
+        .set_auction_schedule(suite.pair.clone(), schedule.clone())
+        .unwrap();
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(1000_u128, suite.pair.0.clone()),
+    );
+
+    // The auction was never opened, so we clean the initial auction and open it
+    let res = suite.crank(pairs.clone(), None).unwrap();
+    assert!(res.has_event(&crank_event(&[], &pair, &pair)));
+    assert_eq!(
+        suite.query_auction_details(auction_addr.clone()).status,
+        ActiveAuctionStatus::Started
+    );
+
+    // Nothing to do while the auction is running
+    let res = suite.crank(pairs.clone(), None).unwrap();
+    assert!(res.has_event(&crank_event(&[], &[], &[])));
+
+    // Finish the auction once it ended
+    suite.update_block(schedule.duration_blocks);
+    let res = suite.crank(pairs.clone(), None).unwrap();
+    assert!(res.has_event(&crank_event(&pair, &[], &[])));
+    assert_eq!(
+        suite.query_auction_details(auction_addr.clone()).status,
+        ActiveAuctionStatus::AuctionClosed
+    );
+
+    // Clean the closed auction, the next one is opened only after the interval
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(1000_u128, suite.pair.0.clone()),
+    );
+    let res = suite.crank(pairs.clone(), None).unwrap();
+    assert!(res.has_event(&crank_event(&[], &pair, &[])));
+
+    suite.update_block(schedule.interval_blocks);
+    let res = suite.crank(pairs.clone(), None).unwrap();
+    assert!(res.has_event(&crank_event(&[], &[], &pair)));
+    assert_eq!(
+        suite.query_auction_details(auction_addr).status,
+        ActiveAuctionStatus::Started
+    );
+}
+
+#[test]
+fn test_crank_sweep_and_isolation() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let auction_addr = suite.get_default_auction_addr();
+    let pair = suite.pair.clone();
+    let bad_pair = Pair::from(("bad".to_string(), "pair".to_string()));
+
+    for (i, amount) in [(0, 134_u128), (1, 278), (2, 359)] {
+        suite.auction_funds(
+            suite.get_account_addr(i),
+            auction_addr.clone(),
+            &coins(amount, pair.0.clone()),
+        );
+    }
+    suite
+        .start_auction(
+            pair.clone(),
+            Some(suite.app.block_info().height),
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+
+    suite.update_dust_destination(pair.clone(), Some(DustDestination::LatestProviders));
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(pair.clone(), None).unwrap();
+
+    let pair0_before = suite.get_balance(2, &pair.0.clone());
+    let pair1_before = suite.get_balance(2, &pair.1.clone());
+
+    // The unknown pair fails its check, the dust of the other pair is swept before it's cleaned
+    let res = suite
+        .crank(Some(vec![bad_pair, pair.clone()]), None)
+        .unwrap();
+
+    let crank_event: Event = ValenceEvent::AuctionManagerCrank {
+        finished: vec![],
+        swept: vec![pair.clone()],
+        cleaned: vec![pair.clone()],
+        opened: vec![],
+    }
+    .into();
+    assert!(res.has_event(&Event::new("wasm-valence-event").add_attributes(crank_event.attributes)));
+    assert!(res.events.iter().any(|event| {
+        event.ty == "wasm-valence-event"
+            && event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "auction-manager-step-failed")
+    }));
+
+    // The largest provider got the rounding remainder of the dust
+    assert_eq!(
+        suite.get_balance(2, &pair.0.clone()).amount,
+        pair0_before.amount + Uint128::one()
+    );
+    assert_eq!(
+        suite.get_balance(2, &pair.1.clone()).amount,
+        pair1_before.amount + Uint128::new(2)
+    );
+    assert_eq!(
+        suite.query_auction_details(auction_addr).leftovers,
+        [Uint128::zero(), Uint128::zero()]
+    );
+}
+
+#[test]
+fn test_deprecate_and_remove_auction() {
+    let mut suite = SuiteBuilder::default().with_accounts(2).build_default();
+    let pair = suite.pair.clone();
+    let auction_addr = suite.get_default_auction_addr();
+
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(1000_u128, pair.0.clone()),
+    );
+    suite.start_auction_day(pair.clone()).unwrap();
+    // Funds for the next auction
+    suite.auction_funds(
+        suite.get_account_addr(1),
+        auction_addr.clone(),
+        &coins(1000_u128, pair.0.clone()),
+    );
+
+    // Can't remove a pair that wasn't deprecated
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionNotDeprecated(pair.clone())
+    );
+
+    suite.deprecate_auction(pair.clone()).unwrap();
+
+    // No new funds are accepted
+    let err = suite.auction_funds_err(
+        suite.admin.clone(),
+        auction_addr.clone(),
+        &coins(1000_u128, pair.0.clone()),
+    );
+    assert_eq!(err, auction::error::ContractError::AuctionIsDeprecated);
+    let is_deprecated: bool = suite
+        .app
+        .wrap()
+        .query_wasm_smart(auction_addr.clone(), &auction::msg::QueryMsg::IsDeprecated)
+        .unwrap();
+    assert!(is_deprecated);
+
+    // The running auction must be closed first
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionNotClosed(pair.clone())
+    );
+
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.close_auction(pair.clone(), None).unwrap();
+
+    // The funds of the next auction must be withdrawn first
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionHasFunds(pair.clone())
+    );
+
+    suite
+        .withdraw_funds(suite.get_account_addr(1), auction_addr)
+        .unwrap();
+    suite.remove_auction(pair.clone()).unwrap();
+
+    assert!(!suite
+        .query_auctions_manager_all_pairs()
+        .iter()
+        .any(|(p, _)| *p == pair));
+}
+
+#[test]
+fn test_remove_auction_with_unclaimed_funds() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let pair = suite.pair.clone();
+    let auction_addr = suite.get_default_auction_addr();
+
+    suite.update_settlement_mode(pair.clone(), SettlementMode::Claim);
+    for (i, amount) in [(0, 134_u128), (1, 278), (2, 359)] {
+        suite.auction_funds(
+            suite.get_account_addr(i),
+            auction_addr.clone(),
+            &coins(amount, pair.0.clone()),
+        );
+    }
+    suite
+        .start_auction(
+            pair.clone(),
+            Some(suite.app.block_info().height),
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(pair.clone(), None).unwrap();
+    suite.deprecate_auction(pair.clone()).unwrap();
+
+    // Providers must claim their share first
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionHasUnclaimedSettlements(pair.clone())
+    );
+
+    for i in 0..3 {
+        suite
+            .claim(suite.get_account_addr(i), auction_addr.clone(), 1)
+            .unwrap();
+    }
+
+    // The rounding remainders are rolled into the leftovers, which must be swept first
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionHasLeftovers(pair.clone())
+    );
+
+    suite.update_dust_destination(
+        pair.clone(),
+        Some(DustDestination::Recipient(suite.admin.clone())),
+    );
+    suite.sweep_dust(auction_addr).unwrap();
+    suite.remove_auction(pair.clone()).unwrap();
+
+    assert!(!suite
+        .query_auctions_manager_all_pairs()
+        .iter()
+        .any(|(p, _)| *p == pair));
+}
+
+#[test]
+fn test_remove_auction_with_large_leftovers() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let pair = suite.pair.clone();
+    let auction_addr = suite.get_default_auction_addr();
+
+    for (i, amount) in [(0, 134_u128), (1, 278), (2, 359)] {
+        suite.auction_funds(
+            suite.get_account_addr(i),
+            auction_addr.clone(),
+            &coins(amount, pair.0.clone()),
+        );
+    }
+    suite
+        .start_auction(
+            pair.clone(),
+            Some(suite.app.block_info().height),
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+    suite.deprecate_auction(pair.clone()).unwrap();
+
+    // Only the last auction refunds its leftovers
+    let err: auction::error::ContractError = suite
+        .refund_deprecated_leftovers(pair.clone(), None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, auction::error::ContractError::AuctionNotClosed);
+
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(pair.clone(), None).unwrap();
+
+    // 1 pair.0 and 2 pair.1 are left from rounding, none of them is dust
+    for denom in [pair.0.clone(), pair.1.clone()] {
+        suite.update_min_amount(
+            &denom,
+            MinAmount {
+                send: Uint128::one(),
+                start_auction: Uint128::new(10),
+            },
+        );
+    }
+    let leftovers = suite.query_auction_details(auction_addr.clone()).leftovers;
+    assert_eq!(leftovers, [Uint128::one(), Uint128::new(2)]);
+    assert_eq!(suite.query_auction_dust(auction_addr.clone(), 1), vec![]);
+
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionHasLeftovers(pair.clone())
+    );
+
+    let balances_before: Vec<(Coin, Coin)> = (0..3)
+        .map(|i| {
+            (
+                suite.get_balance(i, &pair.0.clone()),
+                suite.get_balance(i, &pair.1.clone()),
+            )
+        })
+        .collect();
+
+    // Refund the first 2 providers, the leftovers are still being refunded
+    suite
+        .refund_deprecated_leftovers(pair.clone(), Some(2))
+        .unwrap();
+    assert_eq!(
+        suite.query_auction_details(auction_addr.clone()).leftovers,
+        [Uint128::zero(), Uint128::zero()]
+    );
+    assert!(suite.query_dust_sweep(auction_addr.clone()).is_some());
+
+    let err = suite.remove_auction_err(pair.clone());
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionHasLeftovers(pair.clone())
+    );
+
+    // The next sweep finishes the refund
+    suite.sweep_dust(auction_addr.clone()).unwrap();
+    assert!(suite.query_dust_sweep(auction_addr).is_none());
+
+    let (refunded_0, refunded_1) = balances_before.into_iter().enumerate().fold(
+        (Uint128::zero(), Uint128::zero()),
+        |(refunded_0, refunded_1), (i, (before_0, before_1))| {
+            (
+                refunded_0 + suite.get_balance(i as u64, &pair.0.clone()).amount - before_0.amount,
+                refunded_1 + suite.get_balance(i as u64, &pair.1.clone()).amount - before_1.amount,
+            )
+        },
+    );
+    assert_eq!((refunded_0, refunded_1), (leftovers[0], leftovers[1]));
+
+    suite.remove_auction(pair.clone()).unwrap();
+
+    assert!(!suite
+        .query_auctions_manager_all_pairs()
+        .iter()
+        .any(|(p, _)| *p == pair));
+}
+
+#[test]
+fn test_migrate_all_auctions() {
+    let mut suite = Suite::default();
+    let pairs: Vec<Pair> = suite
+        .query_auctions_manager_all_pairs()
+        .into_iter()
+        .map(|(pair, _)| pair)
+        .collect();
+    let new_code_id = suite.app.store_code(auction_contract());
+
+    let err: auctions_manager::error::ContractError = suite
+        .migrate_all_auctions(new_code_id, None, Some(0))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, auctions_manager::error::ContractError::LimitIsZero);
+    assert_eq!(suite.query_migration_progress(), None);
+
+    // Migrate the first batch
+    suite
+        .migrate_all_auctions(new_code_id, None, Some(4))
+        .unwrap();
+    let progress = suite.query_migration_progress().unwrap();
+    assert_eq!(
+        progress,
+        MigrationProgress {
+            code_id: new_code_id,
+            last_migrated: Some(pairs[3].clone()),
+            migrated: 4,
+            finished: false,
+        }
+    );
+
+    // Continue from where we stopped
+    suite
+        .migrate_all_auctions(new_code_id, progress.last_migrated, Some(4))
+        .unwrap();
+    let progress = suite.query_migration_progress().unwrap();
+    assert_eq!(
+        progress,
+        MigrationProgress {
+            code_id: new_code_id,
+            last_migrated: pairs.last().cloned(),
+            migrated: pairs.len() as u64,
+            finished: true,
+        }
+    );
+
+    for (_, auction_addr) in suite.query_auctions_manager_all_pairs() {
+        let contract = suite.app.contract_data(&auction_addr).unwrap();
+        assert_eq!(contract.code_id, new_code_id);
+    }
+
+    // New auctions are created with the new code id
+    let pair = Pair::from(("random".to_string(), "random2".to_string()));
+    let mut init_msg: auction::msg::InstantiateMsg = AuctionInstantiate::default().into();
+    init_msg.pair = pair.clone();
+    suite.init_auction(
+        pair.clone(),
+        init_msg,
+        Some(MinAmount {
+            send: Uint128::new(5),
+            start_auction: Uint128::new(10),
+        }),
+    );
+    let auction_addr = suite.auction_addrs.get(&pair.into()).unwrap();
+    let contract = suite.app.contract_data(auction_addr).unwrap();
+    assert_eq!(contract.code_id, new_code_id);
+}
+
+#[test]
+fn test_auction_templates() {
+    let mut suite = Suite::default();
+    let template = AuctionTemplate {
+        auction_strategy: AuctionStrategy {
+            start_price_perc: 3000,
+            end_price_perc: 1000,
+        },
+        chain_halt_config: ChainHaltConfig {
+            cap: 7200,
+            block_avg: Decimal::from_str("6").unwrap(),
+        },
+        price_freshness_strategy: PriceFreshnessStrategy {
+            limit: Decimal::from_str("3").unwrap(),
+            multipliers: vec![
+                (
+                    Decimal::from_str("2").unwrap(),
+                    Decimal::from_str("2").unwrap(),
+                ),
+                (Decimal::one(), Decimal::from_str("1.5").unwrap()),
+            ],
+        },
+        min_amount: Some(MinAmount {
+            send: Uint128::new(20),
+            start_auction: Uint128::new(40),
+        }),
+    };
+
+    // Can't use a missing template
+    let err: auctions_manager::error::ContractError = suite
+        .apply_template("base", vec![suite.pair.clone()])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::TemplateNotFound("base".to_string())
+    );
+
+    // The template is verified same as a new auction
+    let mut invalid_template = template.clone();
+    invalid_template.auction_strategy.end_price_perc = 0;
+    let err = suite.set_template_err("base", invalid_template);
+    assert_eq!(
+        err,
+        auctions_manager::error::ContractError::AuctionError(
+            AuctionError::InvalidAuctionStrategyEndPrice
+        )
+    );
+
+    // The multipliers are sorted from the oldest day
+    let mut unsorted_template = template.clone();
+    unsorted_template
+        .price_freshness_strategy
+        .multipliers
+        .reverse();
+    suite.set_template("base", unsorted_template);
+    assert_eq!(suite.query_template("base"), template);
+
+    // Create a new auction from the template
+    let pair = Pair::from(("random".to_string(), "random2".to_string()));
+    suite
+        .init_auction_from_template(pair.clone(), "base")
+        .unwrap();
+    let auction_addr = suite.auction_addrs.get(&pair.clone().into()).unwrap();
+
+    let config = suite.query_auction_config(auction_addr.clone());
+    assert_eq!(config.pair, pair);
+    assert_eq!(config.chain_halt_config, template.chain_halt_config);
+    assert_eq!(
+        config.price_freshness_strategy,
+        template.price_freshness_strategy
+    );
+    assert_eq!(
+        suite.query_auction_strategy(auction_addr.clone()),
+        template.auction_strategy
+    );
+    assert_eq!(suite.get_send_min_limit(&pair.0), Uint128::new(20));
+
+    // Update live auctions with the template, the minimum amount of their denoms is kept
+    let pairs = vec![
+        Pair::from((ATOM.to_string(), NTRN.to_string())),
+        Pair::from((NTRN.to_string(), OSMO.to_string())),
+    ];
+    suite.apply_template("base", pairs.clone()).unwrap();
+
+    for pair in pairs {
+        let auction_addr = suite.auction_addrs.get(&pair.clone().into()).unwrap();
+        let config = suite.query_auction_config(auction_addr.clone());
+        assert_eq!(config.chain_halt_config, template.chain_halt_config);
+        assert_eq!(
+            config.price_freshness_strategy,
+            template.price_freshness_strategy
+        );
+        assert_eq!(
+            suite.query_auction_strategy(auction_addr.clone()),
+            template.auction_strategy
+        );
+        assert_eq!(suite.get_send_min_limit(&pair.0), Uint128::new(5));
+    }
+}
@@ -1,5 +1,6 @@
 use auction::state::ActiveAuctionStatus;
-use cosmwasm_std::{coins, testing::mock_env, Uint128};
+use auction_package::{DustDestination, SettlementMode};
+use cosmwasm_std::{coin, coins, testing::mock_env, Uint128};
 
 use crate::suite::{
     suite::{Suite, DEFAULT_BALANCE_AMOUNT},
@@ -370,6 +371,277 @@ fn test_auction_rounding_leftovers() {
     assert_eq!(active_auction.resolved_amount, Uint128::from(2_u128)) // 2 leftover pair.1
 }
 
+#[test]
+fn test_sweep_dust() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let auction_addr = suite.get_default_auction_addr();
+    let provider3 = suite.get_account_addr(2);
+
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(134_u128, suite.pair.0.clone()),
+    );
+    suite.auction_funds(
+        suite.get_account_addr(1),
+        auction_addr.clone(),
+        &coins(278_u128, suite.pair.0.clone()),
+    );
+    suite.auction_funds(
+        provider3.clone(),
+        auction_addr.clone(),
+        &coins(359_u128, suite.pair.0.clone()),
+    );
+
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            Some(mock_env().block.height),
+            mock_env().block.height + 1000,
+        )
+        .unwrap();
+
+    // Can't sweep while the auction is running
+    let err = suite.sweep_dust_err(auction_addr.clone());
+    assert_eq!(err, auction::error::ContractError::AuctionNotClosed);
+
+    // Providers are only saved when the dust is redistributed to them
+    suite.update_dust_destination(suite.pair.clone(), Some(DustDestination::LatestProviders));
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    // 1 pair.0 and 2 pair.1 are left from rounding, both below the min send amount
+    let dust = vec![
+        coin(1_u128, suite.pair.0.clone()),
+        coin(2_u128, suite.pair.1.clone()),
+    ];
+    assert_eq!(suite.query_auction_dust(auction_addr.clone(), 1), dust);
+
+    suite.update_dust_destination(suite.pair.clone(), None);
+    let err = suite.sweep_dust_err(auction_addr.clone());
+    assert_eq!(err, auction::error::ContractError::NoDustDestination);
+
+    // Redistribute to the providers, the largest provider gets the rounding remainder
+    suite.update_dust_destination(suite.pair.clone(), Some(DustDestination::LatestProviders));
+    let pair0_before = suite.get_balance(2, &suite.pair.0.clone());
+    let pair1_before = suite.get_balance(2, &suite.pair.1.clone());
+    suite.sweep_dust(auction_addr.clone()).unwrap();
+
+    assert_eq!(
+        suite.get_balance(2, &suite.pair.0.clone()).amount,
+        pair0_before.amount + Uint128::one()
+    );
+    assert_eq!(
+        suite.get_balance(2, &suite.pair.1.clone()).amount,
+        pair1_before.amount + Uint128::new(2)
+    );
+
+    let active_auction = suite.query_auction_details(auction_addr.clone());
+    assert_eq!(active_auction.leftovers, [Uint128::zero(), Uint128::zero()]);
+    assert_eq!(suite.query_auction_dust(auction_addr.clone(), 1), dust);
+
+    let err = suite.sweep_dust_err(auction_addr.clone());
+    assert_eq!(err, auction::error::ContractError::NoDustToSweep);
+
+    // Swept dust is not rolled into the next auction
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(100_u128, suite.pair.0.clone()),
+    );
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            None,
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+
+    let active_auction = suite.query_auction_details(auction_addr);
+    assert_eq!(active_auction.total_amount, Uint128::from(100_u128));
+    assert_eq!(active_auction.resolved_amount, Uint128::zero());
+}
+
+#[test]
+fn test_sweep_dust_after_clean_in_batches() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let auction_addr = suite.get_default_auction_addr();
+
+    for (i, amount) in [(0, 134_u128), (1, 278), (2, 359)] {
+        suite.auction_funds(
+            suite.get_account_addr(i),
+            auction_addr.clone(),
+            &coins(amount, suite.pair.0.clone()),
+        );
+    }
+
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            Some(mock_env().block.height),
+            mock_env().block.height + 1000,
+        )
+        .unwrap();
+
+    // Providers are only saved when the dust is redistributed to them
+    suite.update_dust_destination(suite.pair.clone(), Some(DustDestination::LatestProviders));
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    // The providers are known after the funds of the auction were cleaned
+    suite.clean_last_auction(auction_addr.clone());
+    let pair0_before = suite.get_balance(2, &suite.pair.0.clone());
+    let pair1_before = suite.get_balance(2, &suite.pair.1.clone());
+
+    // Every call sends the dust to a single provider, the largest provider is paid last
+    let mut calls = 0;
+    loop {
+        suite
+            .sweep_dust_with_limit(auction_addr.clone(), Some(1))
+            .unwrap();
+        calls += 1;
+
+        if suite.query_dust_sweep(auction_addr.clone()).is_none() {
+            break;
+        }
+    }
+    assert_eq!(calls, 4);
+
+    assert_eq!(
+        suite.get_balance(2, &suite.pair.0.clone()).amount,
+        pair0_before.amount + Uint128::one()
+    );
+    assert_eq!(
+        suite.get_balance(2, &suite.pair.1.clone()).amount,
+        pair1_before.amount + Uint128::new(2)
+    );
+
+    let err = suite.sweep_dust_err(auction_addr);
+    assert_eq!(err, auction::error::ContractError::NoDustToSweep);
+}
+
+#[test]
+fn test_dust_providers_cleaned() {
+    let mut suite = Suite::default();
+    let auction_addr = suite.get_default_auction_addr();
+    let funds = coins(400_u128, suite.pair.0.clone());
+
+    // Providers are not saved when the dust is not redistributed to them
+    suite.auction_funds(suite.get_account_addr(0), auction_addr.clone(), &funds);
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            None,
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+    suite.update_block(500);
+    suite.do_full_bid(400_u128);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+    assert_eq!(
+        suite.query_dust_providers_total(auction_addr.clone(), 1),
+        None
+    );
+    suite.clean_last_auction(auction_addr.clone());
+
+    // Providers of an auction without dust are cleaned with the auction
+    suite.update_dust_destination(suite.pair.clone(), Some(DustDestination::LatestProviders));
+    suite.auction_funds(suite.get_account_addr(0), auction_addr.clone(), &funds);
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            None,
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+    suite.update_block(500);
+    suite.do_full_bid(400_u128);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    let providers_total = suite
+        .query_dust_providers_total(auction_addr.clone(), 2)
+        .unwrap();
+    assert_eq!(providers_total.total, funds[0].amount);
+    assert!(suite.query_auction_dust(auction_addr.clone(), 2).is_empty());
+
+    suite.clean_last_auction(auction_addr.clone());
+    assert_eq!(suite.query_dust_providers_total(auction_addr, 2), None);
+}
+
+#[test]
+fn test_claim_settlement_remainder() {
+    let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
+    let auction_addr = suite.get_default_auction_addr();
+    let providers = [
+        (suite.get_account_addr(0), 134_u128),
+        (suite.get_account_addr(1), 278_u128),
+        (suite.get_account_addr(2), 359_u128),
+    ];
+
+    suite.update_settlement_mode(suite.pair.clone(), SettlementMode::Claim);
+
+    for (provider, amount) in providers.iter() {
+        suite.auction_funds(
+            provider.clone(),
+            auction_addr.clone(),
+            &coins(*amount, suite.pair.0.clone()),
+        );
+    }
+
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            Some(mock_env().block.height),
+            mock_env().block.height + 1000,
+        )
+        .unwrap();
+
+    suite.update_block(500);
+    suite.do_full_bid(770_u128);
+    suite.update_block(500);
+    suite.close_auction(suite.pair.clone(), None).unwrap();
+
+    // Remainders are only known after everyone claimed
+    let active_auction = suite.query_auction_details(auction_addr.clone());
+    assert_eq!(active_auction.leftovers, [Uint128::zero(), Uint128::zero()]);
+
+    for (i, (provider, _)) in providers.iter().enumerate() {
+        suite
+            .claim(provider.clone(), auction_addr.clone(), 1)
+            .unwrap();
+
+        let active_auction = suite.query_auction_details(auction_addr.clone());
+        if i < providers.len() - 1 {
+            assert_eq!(active_auction.leftovers, [Uint128::zero(), Uint128::zero()]);
+        } else {
+            // Same remainders as push mode, 1 pair.0 and 2 pair.1
+            assert_eq!(active_auction.leftovers, [Uint128::one(), Uint128::new(2)]);
+        }
+    }
+
+    // The remainders are rolled into the next auction
+    suite.auction_funds(
+        suite.get_account_addr(0),
+        auction_addr.clone(),
+        &coins(100_u128, suite.pair.0.clone()),
+    );
+    suite
+        .start_auction(
+            suite.pair.clone(),
+            None,
+            suite.app.block_info().height + 1000,
+        )
+        .unwrap();
+
+    let active_auction = suite.query_auction_details(auction_addr);
+    assert_eq!(active_auction.total_amount, Uint128::from(101_u128));
+    assert_eq!(active_auction.resolved_amount, Uint128::new(2));
+}
+
 #[test]
 fn test_multiple_auctions() {
     let mut suite = SuiteBuilder::default().with_accounts(3).build_default();
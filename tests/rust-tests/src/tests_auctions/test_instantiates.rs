@@ -54,6 +54,8 @@ fn test_instantiate_oracle() {
             auction_manager_addr: manager_addr,
             seconds_allow_manual_change: 60 * 60 * 24 * 2,
             seconds_auction_prices_fresh: 60 * 60 * 24 * 3,
+            max_price_deviation_bps: None,
+            anchor_denom: None,
         }
     )
 }
@@ -88,6 +90,8 @@ fn test_instantiate_auction() {
                     (Decimal::one(), Decimal::from_str("1.5").unwrap()),
                 ],
             },
+            protocol_fee: None,
+            cw20_denoms: vec![],
         }
     )
 }
@@ -1,12 +1,14 @@
-use std::borrow::BorrowMut;
-
-use cosmwasm_std::{coins, Addr, Decimal};
+use auction_package::{helpers::GetPriceResponse, Pair};
+use cosmwasm_std::{coins, Addr, Decimal, Event, Uint128};
 use cw_multi_test::Executor;
 use cw_utils::Expiration;
-use price_oracle::state::PriceStep;
+use price_oracle::{
+    sources::{PriceAggregation, PriceSource, PriceSources},
+    state::{PriceStep, Role, RoleScope, TwapConfig},
+};
 
 use crate::suite::{
-    suite::{Suite, DAY, DEFAULT_BLOCK_TIME},
+    suite::{Suite, ATOM, DAY, DEFAULT_BLOCK_TIME, NTRN, OSMO},
     suite_builder::SuiteBuilder,
 };
 
@@ -112,6 +114,8 @@ fn test_config() {
             auction_manager_addr: suite.auctions_manager_addr,
             seconds_allow_manual_change: 60 * 60 * 24 * 2,
             seconds_auction_prices_fresh: 60 * 60 * 24 * 3,
+            max_price_deviation_bps: None,
+            anchor_denom: None,
         }
     )
 }
@@ -314,6 +318,8 @@ fn test_update_config() {
                 auction_manager_addr: Some(new_addr.to_string()),
                 seconds_allow_manual_change: Some(12),
                 seconds_auction_prices_fresh: Some(455),
+                max_price_deviation_bps: Some(1000),
+                anchor_denom: Some(NTRN.to_string()),
             },
             &[],
         )
@@ -325,78 +331,807 @@ fn test_update_config() {
     assert_eq!(new_oracle_config.auction_manager_addr, new_addr.to_string());
     assert_eq!(new_oracle_config.seconds_allow_manual_change, 12);
     assert_eq!(new_oracle_config.seconds_auction_prices_fresh, 455);
+    assert_eq!(new_oracle_config.max_price_deviation_bps, Some(1000));
+    assert_eq!(new_oracle_config.anchor_denom, Some(NTRN.to_string()));
 }
 
 #[test]
 fn test_local_prices() {
-    let mut suite = Suite::default();
-    let funds = coins(100_u128, suite.pair.0.clone());
+    let mut suite = SuiteBuilder::default().build_basic(false);
+    let pair = suite.pair.clone();
+    let mock_addr = suite.init_mock_price_source(Decimal::one());
+    suite
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::OsmosisPool {
+                pool_address: mock_addr.clone(),
+            }),
+        )
+        .unwrap();
 
-    // do 3 auctions
-    suite.finalize_auction(&funds);
-    suite.finalize_auction(&funds);
-    suite.finalize_auction(&funds);
+    // Day 0: price is 1, the only price didn't hold for any time yet, so it is used as is
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, Decimal::one());
 
-    // Update the price from auction
-    suite.update_price(suite.pair.clone()).unwrap();
+    // Day 2: price is 4, 1 was the price for 2 days,
+    // and 4 is weighted by the 2 days of the previous price: (1 * 2 days + 4 * 2 days) / 4 days
+    suite.update_block(DAY * 2 / DEFAULT_BLOCK_TIME);
+    suite.set_mock_price(mock_addr.clone(), Decimal::percent(400));
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::percent(250)
+    );
 
-    // Register the astro path
-    let path = vec![PriceStep {
-        denom1: suite.pair.0.to_string(),
-        denom2: suite.pair.1.to_string(),
-        pool_address: suite
-            .astro_pools
-            .get(&suite.pair.clone().into())
-            .unwrap()
-            .clone(),
-    }];
+    // Day 3: price is 2, (1 * 2 days + 4 * 1 day + 2 * 1 day) / 4 days = 2
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.set_mock_price(mock_addr.clone(), Decimal::percent(200));
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::percent(200)
+    );
+
+    // Day 4: price is still 2, the 3 days window starts at day 1, so 1 only counts for 1 day:
+    // (1 * 1 day + 4 * 1 day + 2 * 1 day + 2 * 1 day) / 4 days = 9 / 4
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::from_ratio(9_u128, 4_u128)
+    );
+
+    // All prices are kept, from the newest to the oldest
+    let local_prices = suite.query_oracle_local_price(pair);
+    assert_eq!(
+        local_prices
+            .iter()
+            .map(|price| price.price)
+            .collect::<Vec<_>>(),
+        vec![
+            Decimal::percent(200),
+            Decimal::percent(200),
+            Decimal::percent(400),
+            Decimal::one(),
+        ]
+    );
+}
+
+#[test]
+fn test_twap_price() {
+    let mut suite = SuiteBuilder::default().build_basic(false);
+    let pair = suite.pair.clone();
+    let mock_addr = suite.init_mock_price_source(Decimal::one());
     suite
-        .add_astro_path_to_oracle(suite.pair.clone(), path)
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::OsmosisPool {
+                pool_address: mock_addr.clone(),
+            }),
+        )
         .unwrap();
 
-    // Randomize the pool a little to get a "nice" price
-    let mut rng = rand::thread_rng();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, Decimal::one());
 
-    for _ in 0..10 {
-        suite.do_random_swap(
-            rng.borrow_mut(),
-            suite.pair.clone(),
-            100_000_u128,
-            1_000_000_u128,
+    // The price changed a day later, the new price is weighted by the day the previous price held
+    suite.update_block(DAY / DEFAULT_BLOCK_TIME);
+    suite.set_mock_price(mock_addr.clone(), Decimal::bps(20000));
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::bps(15000)
+    );
+
+    // A burst update a block later barely moves the price,
+    // 1 held for a day, 2 for a single block, and 10 is weighted by a single block
+    suite.add_block();
+    suite.set_mock_price(mock_addr, Decimal::bps(100000));
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::from_ratio(
+            DAY as u128 + 12_u128 * DEFAULT_BLOCK_TIME as u128,
+            (DAY + 2 * DEFAULT_BLOCK_TIME) as u128
+        )
+    );
+
+    // Smaller window and buffer
+    let twap_config = TwapConfig {
+        window_seconds: 60,
+        max_prices: 2,
+    };
+    suite
+        .update_oracle_twap_config(pair.clone(), twap_config.clone())
+        .unwrap();
+    assert_eq!(suite.query_oracle_twap_config(pair.clone()), twap_config);
+
+    suite.add_block();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_local_price(pair.clone()).len(), 2);
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        Decimal::bps(100000)
+    );
+
+    let err = suite
+        .update_oracle_twap_config(
+            pair,
+            TwapConfig {
+                window_seconds: 0,
+                max_prices: 2,
+            },
+        )
+        .unwrap_err()
+        .downcast::<price_oracle::error::ContractError>()
+        .unwrap();
+    assert_eq!(err, price_oracle::error::ContractError::InvalidTwapConfig);
+}
+
+fn single_source(source: PriceSource) -> PriceSources {
+    PriceSources {
+        sources: vec![source],
+        aggregation: PriceAggregation::Median,
+        max_deviation: Decimal::percent(10),
+        quorum: 1,
+        max_price_age: None,
+    }
+}
+
+#[test]
+fn test_price_sources() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+    let price = Decimal::bps(12345);
+    let mock_addr = suite.init_mock_price_source(price);
+
+    let sources = vec![
+        PriceSource::OsmosisPool {
+            pool_address: mock_addr.clone(),
+        },
+        PriceSource::Simulation {
+            pool_address: mock_addr.clone(),
+            is_cw20: false,
+            offer_amount: None,
+        },
+        PriceSource::PriceFeed {
+            contract_addr: mock_addr.clone(),
+            feed_id: "atom-ntrn".to_string(),
+        },
+    ];
+
+    // No auction prices yet, so the price is taken from the price source
+    for source in sources {
+        suite
+            .set_oracle_price_sources(pair.clone(), single_source(source.clone()))
+            .unwrap();
+        assert_eq!(
+            suite.query_oracle_price_sources(pair.clone()).sources,
+            vec![source]
         );
+
+        suite.update_price(pair.clone()).unwrap();
+        assert_eq!(suite.query_oracle_local_price(pair.clone())[0].price, price);
     }
 
-    let pool_price = suite.query_astro_pool_price(
+    // Simulate with a smaller offer amount, 100 * 1.2345 returns 123
+    let err = suite.set_oracle_price_sources_err(
+        pair.clone(),
+        single_source(PriceSource::Simulation {
+            pool_address: mock_addr.clone(),
+            is_cw20: false,
+            offer_amount: Some(Uint128::zero()),
+        }),
+    );
+    assert_eq!(err, price_oracle::error::ContractError::PriceSourceIsWrong);
+
+    suite
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::Simulation {
+                pool_address: mock_addr,
+                is_cw20: false,
+                offer_amount: Some(Uint128::new(100)),
+            }),
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        Decimal::percent(123)
+    );
+
+    // Take the price from another oracle
+    let second_oracle = suite.init_second_oracle();
+    suite
+        .app
+        .execute_contract(
+            suite.admin.clone(),
+            second_oracle.clone(),
+            &price_oracle::msg::ExecuteMsg::ManualPriceUpdate {
+                pair: pair.clone(),
+                price: Decimal::bps(20000),
+            },
+            &[],
+        )
+        .unwrap();
+
+    suite
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::Oracle {
+                contract_addr: second_oracle,
+            }),
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        Decimal::bps(20000)
+    );
+
+    // Can't use ourselves as the price source
+    let err = suite.set_oracle_price_sources_err(
+        pair.clone(),
+        single_source(PriceSource::Oracle {
+            contract_addr: suite.oracle_addr.clone(),
+        }),
+    );
+    assert_eq!(err, price_oracle::error::ContractError::PriceSourceIsWrong);
+
+    // Without a price source, we fallback to astroport
+    suite.remove_oracle_price_sources(pair.clone());
+    let err = suite.update_price_err(pair.clone());
+    assert_eq!(err, price_oracle::error::ContractError::NoAstroPath(pair));
+}
+
+#[test]
+fn test_price_sources_aggregation() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+
+    let mock1 = suite.init_mock_price_source(Decimal::bps(10000));
+    let mock2 = suite.init_mock_price_source(Decimal::bps(11000));
+    let outlier = suite.init_mock_price_source(Decimal::bps(50000));
+
+    let mut price_sources = PriceSources {
+        sources: vec![
+            PriceSource::OsmosisPool {
+                pool_address: mock1.clone(),
+            },
+            PriceSource::OsmosisPool {
+                pool_address: mock2,
+            },
+            PriceSource::OsmosisPool {
+                pool_address: outlier,
+            },
+            // mock contract doesn't support oracle query, so this source is never live
+            PriceSource::Oracle {
+                contract_addr: mock1,
+            },
+        ],
+        aggregation: PriceAggregation::Median,
+        max_deviation: Decimal::percent(20),
+        quorum: 2,
+        max_price_age: None,
+    };
+
+    // Median of the live sources is 1.1, the outlier is rejected,
+    // median of the 2 other sources is 1.05
+    suite
+        .set_oracle_price_sources(pair.clone(), price_sources.clone())
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        Decimal::bps(10500)
+    );
+
+    // Without the outlier we don't have a quorum
+    price_sources.quorum = 3;
+    suite
+        .set_oracle_price_sources(pair.clone(), price_sources.clone())
+        .unwrap();
+    let err = suite.update_price_err(pair.clone());
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::PriceQuorumNotReached { live: 2, quorum: 3 }
+    );
+
+    // Quorum can't be more than the amount of sources
+    price_sources.quorum = 5;
+    let err = suite.set_oracle_price_sources_err(pair.clone(), price_sources.clone());
+    assert_eq!(err, price_oracle::error::ContractError::InvalidPriceQuorum);
+
+    // Trim percentage must be less than 50%
+    price_sources.quorum = 2;
+    price_sources.aggregation = PriceAggregation::TrimmedMean {
+        trim_perc: Decimal::percent(50),
+    };
+    let err = suite.set_oracle_price_sources_err(pair, price_sources);
+    assert_eq!(err, price_oracle::error::ContractError::InvalidTrimPerc);
+}
+
+#[test]
+fn test_price_deviation_circuit_breaker() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+    let start_price = suite.query_oracle_price(pair.clone()).price;
+    let mock_addr = suite.init_mock_price_source(start_price);
+
+    suite
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::OsmosisPool {
+                pool_address: mock_addr.clone(),
+            }),
+        )
+        .unwrap();
+    // Only keep the last price, so the price of the pair is the last published price
+    suite
+        .update_oracle_twap_config(
+            pair.clone(),
+            TwapConfig {
+                window_seconds: DAY,
+                max_prices: 1,
+            },
+        )
+        .unwrap();
+    suite.update_oracle_max_price_deviation(1000).unwrap();
+
+    // Price within 10% of the current price is published
+    let price = start_price * Decimal::percent(105);
+    suite.set_mock_price(mock_addr.clone(), price);
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, price);
+    assert_eq!(suite.query_oracle_pending_price(pair.clone()), None);
+
+    // Price that deviates too much is held as pending
+    let manipulated_price = start_price * Decimal::percent(200);
+    suite.set_mock_price(mock_addr.clone(), manipulated_price);
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, price);
+    assert_eq!(
         suite
-            .astro_pools
-            .get(&suite.pair.clone().into())
+            .query_oracle_pending_price(pair.clone())
             .unwrap()
-            .clone(),
-        suite.pair.clone(),
+            .price
+            .price,
+        manipulated_price
     );
 
-    // make sure the last price on local, is not the price from the pool
-    let local_prices = suite.query_oracle_local_price(suite.pair.clone());
-    assert_ne!(local_prices[0].price, pool_price);
+    // Another update in the same block doesn't confirm the pending price
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, price);
+
+    // Price went back to normal, so the pending price is dropped
+    suite.update_block(1);
+    suite.set_mock_price(mock_addr.clone(), price);
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, price);
+    assert_eq!(suite.query_oracle_pending_price(pair.clone()), None);
+
+    // Pending price is confirmed by an update in a later block
+    suite.set_mock_price(mock_addr.clone(), manipulated_price);
+    suite.update_price(pair.clone()).unwrap();
+    suite.update_block(1);
+    suite.update_price(pair.clone()).unwrap();
+    assert_eq!(
+        suite.query_oracle_price(pair.clone()).price,
+        manipulated_price
+    );
+    assert_eq!(suite.query_oracle_pending_price(pair.clone()), None);
 
-    // Update price, should get it from astro
-    suite.update_block(DAY / DEFAULT_BLOCK_TIME * 2);
+    // Admin can reject or approve the pending price
+    let err = suite.approve_pending_price_err(pair.clone());
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::PendingPriceNotFound(pair.clone())
+    );
 
-    // Update the price from auction
-    suite.update_price(suite.pair.clone()).unwrap();
+    suite.set_mock_price(mock_addr, price);
+    suite.update_price(pair.clone()).unwrap();
+    suite.reject_pending_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_pending_price(pair.clone()), None);
+
+    suite.update_price(pair.clone()).unwrap();
+    suite.approve_pending_price(pair.clone()).unwrap();
+    assert_eq!(suite.query_oracle_price(pair.clone()).price, price);
+    assert_eq!(suite.query_oracle_pending_price(pair), None);
+}
 
-    let local_prices = suite.query_oracle_local_price(suite.pair.clone());
+#[test]
+fn test_oracle_roles() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+    let other_pair = Pair::from((ATOM.to_string(), OSMO.to_string()));
+    let keeper = Addr::unchecked("keeper");
+    let signer = Addr::unchecked("signer");
+
+    let mock_addr = suite.init_mock_price_source(Decimal::bps(15000));
+    for pair in [pair.clone(), other_pair.clone()] {
+        suite
+            .set_oracle_price_sources(
+                pair,
+                single_source(PriceSource::OsmosisPool {
+                    pool_address: mock_addr.clone(),
+                }),
+            )
+            .unwrap();
+    }
+
+    // Anyone can update prices while there are no updaters
+    suite
+        .update_price_from(suite.mm.clone(), pair.clone())
+        .unwrap();
+
+    // Updater is scoped to a single pair
+    suite
+        .add_oracle_role(Role::Updater, keeper.clone(), Some(vec![pair.clone()]))
+        .unwrap();
+    assert_eq!(
+        suite.query_oracle_role_members(Role::Updater),
+        vec![(
+            keeper.clone(),
+            RoleScope {
+                pairs: Some(vec![pair.clone()])
+            }
+        )]
+    );
+
+    let err = suite.update_price_from_err(suite.mm.clone(), pair.clone());
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::MissingRole(Role::Updater, pair.clone())
+    );
+
+    suite
+        .update_price_from(keeper.clone(), pair.clone())
+        .unwrap();
+
+    let err = suite.update_price_from_err(keeper.clone(), other_pair.clone());
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::MissingRole(Role::Updater, other_pair.clone())
+    );
+
+    // Admin can always update
+    suite.update_price(other_pair.clone()).unwrap();
+
+    // Manual price signer for all pairs
+    suite.update_block(DAY * 3 / DEFAULT_BLOCK_TIME);
+
+    let err: price_oracle::error::ContractError = suite
+        .manual_update_price_from(keeper.clone(), pair.clone(), Decimal::one())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::MissingRole(Role::ManualPriceSigner, pair.clone())
+    );
+
+    suite
+        .add_oracle_role(Role::ManualPriceSigner, signer.clone(), None)
+        .unwrap();
+    suite
+        .manual_update_price_from(signer.clone(), pair.clone(), Decimal::one())
+        .unwrap();
+    suite
+        .manual_update_price_from(signer, other_pair, Decimal::one())
+        .unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pair.clone())[0].price,
+        Decimal::one()
+    );
+
+    // Removing the last updater makes updates permissionless again
+    suite
+        .remove_oracle_role(Role::Updater, keeper.clone())
+        .unwrap();
+    assert!(suite.query_oracle_role_members(Role::Updater).is_empty());
+
+    let err: price_oracle::error::ContractError = suite
+        .remove_oracle_role(Role::Updater, keeper)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        price_oracle::error::ContractError::RoleNotFound(Role::Updater)
+    );
+
+    suite.update_price_from(suite.mm.clone(), pair).unwrap();
+}
+
+#[test]
+fn test_update_prices_batch() {
+    let mut suite = Suite::default();
+    let pairs: Vec<Pair> = suite
+        .query_auctions_manager_all_pairs()
+        .into_iter()
+        .map(|(pair, _)| pair)
+        .collect();
+    let price = Decimal::bps(12345);
+    let mock_addr = suite.init_mock_price_source(price);
+    let failed_event =
+        Event::new("wasm-valence-event").add_attribute("action", "oracle-update-price-failed");
+
+    // The mock doesn't answer the oracle query, so the first pair fails to update
+    suite
+        .set_oracle_price_sources(
+            pairs[0].clone(),
+            single_source(PriceSource::Oracle {
+                contract_addr: mock_addr.clone(),
+            }),
+        )
+        .unwrap();
+    for pair in pairs.iter().skip(1) {
+        suite
+            .set_oracle_price_sources(
+                pair.clone(),
+                single_source(PriceSource::OsmosisPool {
+                    pool_address: mock_addr.clone(),
+                }),
+            )
+            .unwrap();
+    }
+
+    let err: price_oracle::error::ContractError = suite
+        .update_prices(None, Some(0))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, price_oracle::error::ContractError::LimitIsZero);
+
+    // First batch updates the first 2 pairs, and the failed pair doesn't fail the batch
+    let res = suite.update_prices(None, Some(2)).unwrap();
+    assert!(res.has_event(&failed_event));
+    assert_ne!(
+        suite.query_oracle_local_price(pairs[0].clone())[0].price,
+        price
+    );
+    assert_eq!(
+        suite.query_oracle_local_price(pairs[1].clone())[0].price,
+        price
+    );
+    assert_ne!(
+        suite.query_oracle_local_price(pairs[2].clone())[0].price,
+        price
+    );
+
+    // Next batches continue from the last pair we updated
+    for _ in 1..(pairs.len() + 1) / 2 {
+        let res = suite.update_prices(None, Some(2)).unwrap();
+        assert!(!res.has_event(&failed_event));
+    }
+
+    for pair in pairs.iter().skip(1) {
+        assert_eq!(suite.query_oracle_local_price(pair.clone())[0].price, price);
+    }
 
-    // We should have 2 prices because we updated prices twice
-    assert_eq!(local_prices.len(), 3);
-    // The first price should be the pool price
-    assert_eq!(local_prices[0].price, pool_price);
+    // We went over all pairs, so we start from the beginning again
+    let res = suite.update_prices(None, Some(2)).unwrap();
+    assert!(res.has_event(&failed_event));
 
-    // make sure the actual price is the avg of the local prices
-    let oracle_price = suite.query_oracle_price(suite.pair.clone());
+    // Update specific pairs
+    suite.update_block(1);
+    suite.set_mock_price(mock_addr, Decimal::bps(20000));
+    suite
+        .update_prices(Some(vec![pairs[3].clone()]), None)
+        .unwrap();
+    assert_eq!(
+        suite.query_oracle_local_price(pairs[3].clone())[0].price,
+        Decimal::bps(20000)
+    );
     assert_eq!(
-        oracle_price.price,
-        (local_prices[0].price + local_prices[1].price + local_prices[2].price)
-            / Decimal::from_atomics(3_u128, 0).unwrap()
+        suite.query_oracle_local_price(pairs[4].clone())[0].price,
+        price
     );
 }
+
+#[test]
+fn test_derived_prices() {
+    let mut suite = SuiteBuilder::default().build_basic(false);
+    let atom_ntrn = Pair::from((ATOM.to_string(), NTRN.to_string()));
+    let ntrn_atom = Pair::from((NTRN.to_string(), ATOM.to_string()));
+    let atom_osmo = Pair::from((ATOM.to_string(), OSMO.to_string()));
+    let osmo_ntrn = Pair::from((OSMO.to_string(), NTRN.to_string()));
+
+    suite
+        .manual_update_price(atom_ntrn.clone(), Decimal::bps(15000))
+        .unwrap();
+    let atom_ntrn_time = suite.app.block_info().time;
+
+    let price = suite.query_oracle_price_details(atom_ntrn.clone());
+    assert_eq!(price.price, Decimal::bps(15000));
+    assert!(!price.derived);
+
+    // Inverse of the stored pair
+    let price = suite.query_oracle_price_details(ntrn_atom.clone());
+    assert_eq!(price.price, Decimal::one() / Decimal::bps(15000));
+    assert_eq!(price.time, atom_ntrn_time);
+    assert!(price.derived);
+
+    // GetPrice returns the same price without the derived flag
+    assert_eq!(
+        suite.query_oracle_price(ntrn_atom),
+        GetPriceResponse {
+            price: price.price,
+            time: price.time,
+        }
+    );
+
+    // No anchor denom, so we can't derive a cross price
+    let err = suite
+        .app
+        .wrap()
+        .query_wasm_smart::<GetPriceResponse>(
+            suite.oracle_addr.clone(),
+            &price_oracle::msg::QueryMsg::GetPrice {
+                pair: atom_osmo.clone(),
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &price_oracle::error::ContractError::PriceNotFound(atom_osmo.clone()).to_string()
+    ));
+
+    // ATOM/OSMO = ATOM/NTRN * NTRN/OSMO, with the time of the oldest price
+    suite.update_block(10);
+    suite
+        .manual_update_price(osmo_ntrn, Decimal::percent(50))
+        .unwrap();
+    suite.update_oracle_anchor_denom(NTRN).unwrap();
+
+    let price = suite.query_oracle_price_details(atom_osmo);
+    assert_eq!(price.price, Decimal::from_ratio(3_u128, 1_u128));
+    assert_eq!(price.time, atom_ntrn_time);
+    assert!(price.derived);
+}
+
+#[test]
+fn test_price_history() {
+    let mut suite = Suite::default();
+    let pair = suite.pair.clone();
+    let start_price = suite.query_oracle_price(pair.clone()).price;
+
+    // The default suite sets the first price manually
+    let history = suite.query_oracle_price_history(pair.clone(), None, None);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, 0);
+    assert_eq!(history[0].1.price, start_price);
+    assert_eq!(history[0].1.average_price, start_price);
+    assert_eq!(history[0].1.source, "manual");
+
+    let mock_addr = suite.init_mock_price_source(start_price * Decimal::percent(110));
+    suite
+        .set_oracle_price_sources(
+            pair.clone(),
+            single_source(PriceSource::OsmosisPool {
+                pool_address: mock_addr.clone(),
+            }),
+        )
+        .unwrap();
+    suite.update_price(pair.clone()).unwrap();
+    suite.update_block(10);
+    suite.set_mock_price(mock_addr, start_price * Decimal::percent(120));
+    suite.update_price(pair.clone()).unwrap();
+
+    let history = suite.query_oracle_price_history(pair.clone(), None, Some(2));
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].0, 1);
+    assert_eq!(history[1].1.price, start_price * Decimal::percent(110));
+    assert_eq!(history[1].1.source, "osmosis-pool");
+
+    let history = suite.query_oracle_price_history(pair.clone(), Some(1), None);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, 2);
+    assert_eq!(history[0].1.price, start_price * Decimal::percent(120));
+    assert_eq!(history[0].1.time, suite.app.block_info().time);
+    assert_eq!(
+        history[0].1.average_price,
+        suite.query_oracle_price(pair).price
+    );
+
+    // The history is kept per pair
+    let other_pair = Pair::from((ATOM.to_string(), OSMO.to_string()));
+    let history = suite.query_oracle_price_history(other_pair, None, None);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].1.source, "manual");
+}
+
+#[test]
+fn test_stale_prices_and_health() {
+    let mut suite = SuiteBuilder::default().build_basic(false);
+    let pairs: Vec<Pair> = suite
+        .query_auctions_manager_all_pairs()
+        .into_iter()
+        .map(|(pair, _)| pair)
+        .collect();
+
+    // Nothing is set up yet, every pair needs attention
+    let health = suite.query_oracle_health(None, None);
+    assert_eq!(health.no_price, pairs);
+    assert_eq!(health.no_astro_path, pairs);
+    assert_eq!(health.auction_twap_too_short, pairs);
+    assert!(health.past_manual_change.is_empty());
+
+    for pair in pairs.iter() {
+        suite
+            .manual_update_price(pair.clone(), Decimal::one())
+            .unwrap();
+    }
+    let pair = suite.pair.clone();
+    let other_pairs: Vec<Pair> = pairs.iter().filter(|p| **p != pair).cloned().collect();
+    let pool_address = suite.astro_pools.get(&pair.clone().into()).unwrap().clone();
+    suite
+        .add_astro_path_to_oracle(
+            pair.clone(),
+            vec![PriceStep {
+                denom1: pair.0.clone(),
+                denom2: pair.1.clone(),
+                pool_address,
+                offer_amount: None,
+                price_query: None,
+            }],
+        )
+        .unwrap();
+
+    let health = suite.query_oracle_health(None, None);
+    assert!(health.no_price.is_empty());
+    assert_eq!(health.no_astro_path, other_pairs);
+    assert!(health.past_manual_change.is_empty());
+    assert!(suite
+        .query_oracle_stale_prices(DAY, None, None)
+        .prices
+        .is_empty());
+
+    // After 2 days, all prices are stale and can be updated manually
+    suite.update_block(2 * DAY / DEFAULT_BLOCK_TIME);
+    suite
+        .manual_update_price(pair.clone(), Decimal::one())
+        .unwrap();
+
+    let stale_pairs: Vec<Pair> = suite
+        .query_oracle_stale_prices(DAY, None, None)
+        .prices
+        .into_iter()
+        .map(|(pair, _)| pair)
+        .collect();
+    assert_eq!(stale_pairs, other_pairs);
+
+    let health = suite.query_oracle_health(None, None);
+    assert_eq!(health.past_manual_change, other_pairs);
+    assert_eq!(health.last_checked, None);
+
+    // Paginate over the stale prices and the health of the pairs
+    // The limit is the amount of prices checked, so a page can have no stale prices
+    let mut start_after = None;
+    let mut stale_prices = vec![];
+    loop {
+        let page = suite.query_oracle_stale_prices(DAY, start_after, Some(1));
+        assert!(page.prices.len() <= 1);
+        stale_prices.extend(page.prices.into_iter().map(|(pair, _)| pair));
+
+        match page.last_checked {
+            Some(last_checked) => start_after = Some(last_checked),
+            None => break,
+        }
+    }
+    assert_eq!(stale_prices, other_pairs);
+
+    let mut start_after = None;
+    let mut past_manual_change = vec![];
+    loop {
+        let health = suite.query_oracle_health(start_after, Some(1));
+        assert!(health.past_manual_change.len() <= 1);
+        past_manual_change.extend(health.past_manual_change);
+
+        match health.last_checked {
+            Some(last_checked) => start_after = Some(last_checked),
+            None => break,
+        }
+    }
+    assert_eq!(past_manual_change, other_pairs);
+}
@@ -1,12 +1,16 @@
 use std::{collections::HashSet, str::FromStr};
 
-use auction_package::Pair;
+use auction_package::{states::MinAmount, AuctionStrategy, Pair};
 use cosmwasm_std::{Decimal, Event, Uint128};
 
-use valence_package::services::rebalancer::PID;
+use valence_package::services::{
+    rebalancer::{Target, PID},
+    ValenceServices,
+};
 
 use crate::suite::{
-    suite::{Suite, ATOM, NTRN},
+    instantiates::AuctionInstantiate,
+    suite::{Suite, ATOM, DEFAULT_BALANCE_AMOUNT, NTRN},
     suite_builder::SuiteBuilder,
 };
 
@@ -138,3 +142,88 @@ fn test_targets_saved_after_rebalance() {
         .unwrap();
     assert!(config.targets[0].last_input.is_some());
 }
+
+#[test]
+fn test_rebalance_cw20_target() {
+    let mut suite = SuiteBuilder::default().with_accounts(1).build_basic(true);
+    let account = suite.get_account_addr(0);
+    // Hold more of the token than the atom of the account
+    let token_amount = DEFAULT_BALANCE_AMOUNT * Uint128::new(3);
+    let token = suite.init_cw20_token(vec![(account.clone(), token_amount.u128())]);
+
+    // Create auctions for both sides of the cw20 token
+    for pair in [
+        Pair::from((ATOM.to_string(), token.to_string())),
+        Pair::from((token.to_string(), ATOM.to_string())),
+    ] {
+        let mut init_msg = AuctionInstantiate::new(
+            pair.clone(),
+            AuctionStrategy {
+                start_price_perc: 2000,
+                end_price_perc: 2000,
+            },
+        );
+        init_msg.change_cw20_denoms(vec![token.to_string()]);
+
+        let min_amount = (pair.0 == token.to_string()).then_some(MinAmount {
+            send: Uint128::new(5),
+            start_auction: Uint128::new(10),
+        });
+        suite.init_auction(pair.clone(), init_msg.into(), min_amount);
+        suite.manual_update_price(pair, Decimal::one()).unwrap();
+    }
+
+    // Only use atom as a base denom, and whitelist the cw20 token
+    suite
+        .update_rebalancer_base_denom_whitelist(suite.admin.clone(), vec![], vec![NTRN.to_string()])
+        .unwrap();
+    suite
+        .update_rebalancer_denom_whitelist(suite.admin.clone(), vec![token.to_string()], vec![])
+        .unwrap();
+
+    suite
+        .add_service_to_manager(
+            suite.admin.clone(),
+            suite.manager_addr.clone(),
+            ValenceServices::Rebalancer,
+            suite.rebalancer_addr.to_string(),
+        )
+        .unwrap();
+
+    let mut config = SuiteBuilder::get_default_rebalancer_register_data();
+    config.targets = HashSet::from([
+        Target {
+            denom: ATOM.to_string(),
+            bps: 5000,
+            min_balance: None,
+        },
+        Target {
+            denom: token.to_string(),
+            bps: 5000,
+            min_balance: None,
+        },
+    ]);
+    suite
+        .register_to_service(suite.owner.clone(), 0, ValenceServices::Rebalancer, config)
+        .unwrap();
+
+    let atom_balance = suite.get_balance(0, ATOM);
+
+    suite.rebalance(None).unwrap();
+
+    // The account holds more of the cw20 token than its target, so it should
+    // sell the token and keep its atom
+    let token_balance = suite.query_cw20_balance(token.clone(), account);
+    assert!(token_balance < token_amount);
+    assert_eq!(suite.get_balance(0, ATOM), atom_balance);
+
+    let auction_addr = suite
+        .auction_addrs
+        .get(&Pair::from((token.to_string(), ATOM.to_string())).into())
+        .unwrap()
+        .clone();
+    assert_eq!(
+        suite.query_cw20_balance(token, auction_addr),
+        token_amount - token_balance
+    );
+}